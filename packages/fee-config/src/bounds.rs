@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+use crate::FeeConfigError;
+
+#[cw_serde]
+#[derive(Default)]
+/// The minimum and maximum fee that is taken from a single asset.
+pub struct FeeBounds {
+    /// The smallest fee that is taken. Amounts smaller than this are rejected.
+    pub min: Option<Uint128>,
+    /// The largest fee that is taken.
+    pub max: Option<Uint128>,
+}
+
+impl FeeBounds {
+    /// Validates that the minimum fee is not larger than the maximum fee.
+    pub fn validate(&self) -> Result<(), FeeConfigError> {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(FeeConfigError::MinFeeAboveMaxFee { min, max });
+            }
+        }
+        Ok(())
+    }
+
+    /// Clamps `fee`, which is taken from `amount`, to the bounds. Returns an
+    /// error if `amount` is non-zero but smaller than the minimum fee.
+    pub fn apply(&self, amount: Uint128, fee: Uint128) -> Result<Uint128, FeeConfigError> {
+        if amount.is_zero() {
+            return Ok(Uint128::zero());
+        }
+        let mut fee = fee;
+        if let Some(min) = self.min {
+            if amount < min {
+                return Err(FeeConfigError::AmountBelowMinFee {
+                    amount,
+                    min_fee: min,
+                });
+            }
+            fee = fee.max(min);
+        }
+        if let Some(max) = self.max {
+            fee = fee.min(max);
+        }
+        Ok(fee)
+    }
+}
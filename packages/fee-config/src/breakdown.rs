@@ -0,0 +1,89 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Env};
+use cw_asset::{AssetInfo, AssetList};
+
+use crate::{FeeConfigError, FeeRecipient};
+
+#[cw_serde]
+#[derive(Default)]
+/// A breakdown of the fees taken from a set of assets and how they are split
+/// between the fee recipients. Calculating a breakdown does not create any
+/// messages, which makes it suitable for simulations and events.
+pub struct FeeBreakdown {
+    /// The total fee taken from the input assets.
+    pub fees: AssetList,
+    /// The amounts sent to each fee recipient, in the order of
+    /// `fee_recipients`. The contract itself is not included.
+    pub recipients: Vec<(FeeRecipient<Addr>, AssetList)>,
+    /// The part of the fees that is not sent anywhere and stays in the
+    /// contract. This is the contract's own share, any amount left over due to
+    /// rounding and the shares in assets that a recipient can't receive.
+    pub retained: AssetList,
+    /// The input assets after the fees have been deducted.
+    pub assets_after_fees: AssetList,
+}
+
+impl FeeBreakdown {
+    /// Returns the amounts sent to the recipient with the specified address.
+    pub fn recipient_amounts(&self, recipient: &Addr) -> AssetList {
+        self.fee_recipient_amounts(&recipient.clone().into())
+    }
+
+    /// Returns the amounts sent to the specified fee recipient, e.g. the
+    /// burned amounts for `FeeRecipient::Burn`. Recipients with an address are
+    /// matched by their address.
+    pub fn fee_recipient_amounts(&self, recipient: &FeeRecipient<Addr>) -> AssetList {
+        self.recipients
+            .iter()
+            .find(|(other, _)| other.is_same(recipient))
+            .map(|(_, assets)| assets.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the effective fee rate of each asset in `fees`, i.e. the fee
+    /// divided by the amount it was taken from. This reflects rate overrides,
+    /// fee tiers, fee bounds and rounding. Assets are in the order of `fees`.
+    pub fn fee_rates(&self) -> Vec<(AssetInfo, Decimal)> {
+        self.fees
+            .into_iter()
+            .map(|fee| {
+                let after_fees = self
+                    .assets_after_fees
+                    .find(&fee.info)
+                    .map(|asset| asset.amount)
+                    .unwrap_or_default();
+                let rate = Decimal::from_ratio(fee.amount, fee.amount.saturating_add(after_fees));
+                (fee.info.clone(), rate)
+            })
+            .collect()
+    }
+
+    /// Creates messages to transfer the fees to the fee recipients. `env` is
+    /// used to calculate the timeout of IBC transfers. The native fees of each
+    /// recipient are combined into a single message, see
+    /// `FeeRecipient::transfer_msgs`.
+    pub fn transfer_msgs(&self, env: &Env) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        Ok(self
+            .recipients
+            .iter()
+            .map(|(recipient, assets)| recipient.transfer_msgs(assets, env))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Creates messages to transfer the fees to the fee recipients like
+    /// `transfer_msgs`, but with one message per asset for each address, see
+    /// `FeeRecipient::transfer_msgs_per_asset`.
+    pub fn transfer_msgs_per_asset(&self, env: &Env) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        Ok(self
+            .recipients
+            .iter()
+            .map(|(recipient, assets)| recipient.transfer_msgs_per_asset(assets, env))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
@@ -0,0 +1,150 @@
+use cosmwasm_std::{
+    CoinsError, ConversionOverflowError, Decimal, DivideByZeroError, OverflowError, StdError,
+    Uint128,
+};
+use cw_asset::{Asset, AssetError, AssetInfo, AssetList};
+use thiserror::Error;
+
+/// Errors returned when validating a fee config or calculating fees.
+///
+/// Converts into `StdError`. Contract errors that wrap `StdError` need their
+/// own `#[from] FeeConfigError` variant to use `?` on these errors, or must
+/// convert them with `.map_err(StdError::from)`.
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeConfigError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    DivideByZero(#[from] DivideByZeroError),
+
+    #[error("{0}")]
+    Coins(#[from] CoinsError),
+
+    #[error("Fee rate can't be higher than 100%, got {rate}")]
+    RateTooHigh { rate: Decimal },
+
+    #[error("Sum of fee recipient percentages must be 100%, got {sum}")]
+    WeightsDoNotSumToOne { sum: Decimal },
+
+    #[error("Fee recipient percentages must be greater than zero, got zero for {recipient}")]
+    ZeroWeight { recipient: String },
+
+    #[error("Fee rate {rate} exceeds the maximum fee rate {max}")]
+    FeeRateAboveLimit { rate: Decimal, max: Decimal },
+
+    #[error("Number of fee recipients {count} exceeds the maximum of {max}")]
+    TooManyRecipients { count: usize, max: u32 },
+
+    #[error("Fee recipient weight {weight} of {recipient} is not a multiple of {granularity}")]
+    WeightGranularity {
+        recipient: String,
+        weight: Decimal,
+        granularity: Decimal,
+    },
+
+    #[error("Fee recipient weight granularity must be greater than zero")]
+    ZeroWeightGranularity,
+
+    #[error("Fee recipient weights must either all be shares or all be percentages")]
+    MixedWeights,
+
+    #[error("Sum of fee recipient shares overflows")]
+    SharesOverflow,
+
+    #[error("Duplicate fee recipient {recipient}")]
+    DuplicateRecipient { recipient: String },
+
+    #[error("Fee recipient {recipient} not found")]
+    RecipientNotFound { recipient: String },
+
+    #[error("Invalid address {address}. Error: {source}")]
+    InvalidAddress { address: String, source: StdError },
+
+    #[error("Invalid IBC channel id {channel_id}")]
+    InvalidChannelId { channel_id: String },
+
+    #[error("Invalid remote address {address}. Error: {reason}")]
+    InvalidRemoteAddress { address: String, reason: String },
+
+    #[error("IBC fee recipients must have a timeout greater than zero")]
+    ZeroIbcTimeout,
+
+    #[error("Fee recipient {recipient} can't receive asset {asset}")]
+    UnsupportedAsset { recipient: String, asset: AssetInfo },
+
+    #[error("Invalid asset {asset}. Error: {source}")]
+    InvalidAsset { asset: String, source: AssetError },
+
+    #[error("Duplicate {kind} for asset {asset}")]
+    DuplicateAsset { kind: String, asset: AssetInfo },
+
+    #[error("Asset {asset} can't have both fee tiers and a fee rate override")]
+    TiersAndRateOverride { asset: AssetInfo },
+
+    #[error(
+        "Asset {asset} is exempted from fees by a zero fee rate override but has a minimum fee"
+    )]
+    MinFeeForExemptAsset { asset: AssetInfo },
+
+    #[error("Minimum fee {min} can't be larger than maximum fee {max}")]
+    MinFeeAboveMaxFee { min: Uint128, max: Uint128 },
+
+    #[error("Amount {amount} is smaller than the minimum fee {min_fee}")]
+    AmountBelowMinFee { amount: Uint128, min_fee: Uint128 },
+
+    #[error("Fee tiers can't be empty")]
+    EmptyFeeTiers,
+
+    #[error("The first fee tier must have a threshold of zero")]
+    FirstTierThresholdNotZero,
+
+    #[error("Fee tier thresholds must be strictly increasing")]
+    TierThresholdsNotIncreasing,
+
+    #[error("Fee schedule can't be empty")]
+    EmptyFeeSchedule,
+
+    #[error("Fee schedule entries must have strictly increasing start times or heights")]
+    FeeScheduleNotIncreasing,
+
+    #[error("Can't gross up an amount when the fee rate is 100%")]
+    GrossUpWithFullFeeRate,
+
+    #[error("Can't gross up {net} without overflowing")]
+    GrossUpOverflow { net: Uint128 },
+
+    #[error("Failed to convert Asset {asset} to Coin. Error: {source}")]
+    AssetConversion { asset: Asset, source: AssetError },
+
+    #[error("Failed to create transfer messages for AssetList {assets}. Error: {source}")]
+    TransferMsgs {
+        assets: AssetList,
+        source: AssetError,
+    },
+
+    #[error("Failed to deduct fees from AssetList {assets}. Error: {source}")]
+    DeductFees {
+        assets: AssetList,
+        source: AssetError,
+    },
+}
+
+impl From<FeeConfigError> for StdError {
+    fn from(err: FeeConfigError) -> Self {
+        match err {
+            FeeConfigError::Std(e) => e,
+            FeeConfigError::Overflow(e) => e.into(),
+            FeeConfigError::ConversionOverflow(e) => e.into(),
+            FeeConfigError::DivideByZero(e) => e.into(),
+            FeeConfigError::Coins(e) => e.into(),
+            e => StdError::generic_err(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Env, Event};
+use cw_asset::{AssetInfo, AssetList};
+
+use crate::{FeeBreakdown, FeeConfig, FeeConfigError};
+
+/// The type of the event emitted when fees are collected.
+pub const FEE_COLLECTED_EVENT: &str = "fee_collected";
+
+impl FeeBreakdown {
+    /// Creates a `fee_collected` event describing the breakdown. The event has
+    /// the following attributes, in this order:
+    /// * `payer` - The address that paid the fees, if specified.
+    /// * `fees` - The total fees taken, e.g. `native:uusdc:10,cw20:addr:5`.
+    /// * `fee_rate` - The effective fee rate of each asset in `fees`, see
+    ///   `fee_rates`, e.g. `native:uusdc:0.01,cw20:addr:0.005`.
+    /// * `recipient` and `amount` - One pair per fee recipient, with the
+    ///   recipient and the assets sent to it.
+    /// * `retained` - The fees that stay in the contract.
+    ///
+    /// Asset lists are formatted like `AssetList`'s `Display` implementation,
+    /// which prints `[]` for an empty list. The fee rates follow the same
+    /// format.
+    pub fn fee_collected_event(&self, payer: Option<&Addr>) -> Event {
+        let mut event = Event::new(FEE_COLLECTED_EVENT);
+        if let Some(payer) = payer {
+            event = event.add_attribute("payer", payer);
+        }
+        event = event
+            .add_attribute("fees", self.fees.to_string())
+            .add_attribute("fee_rate", format_fee_rates(&self.fee_rates()));
+        for (recipient, assets) in &self.recipients {
+            event = event
+                .add_attribute("recipient", recipient.to_string())
+                .add_attribute("amount", assets.to_string());
+        }
+        event.add_attribute("retained", self.retained.to_string())
+    }
+}
+
+/// Formats fee rates per asset like `AssetList` formats amounts.
+fn format_fee_rates(rates: &[(AssetInfo, Decimal)]) -> String {
+    if rates.is_empty() {
+        return "[]".to_string();
+    }
+    rates
+        .iter()
+        .map(|(info, rate)| format!("{info}:{rate}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl FeeConfig<Addr> {
+    /// Calculates the fee from the input assets like `fee_msgs_from_assets`
+    /// and also returns a `fee_collected` event describing it, see
+    /// `FeeBreakdown::fee_collected_event`. If a payer is specified, fee
+    /// exemptions are applied like in `fee_msgs_from_assets_for_payer`.
+    pub fn fee_msgs_and_event_from_assets(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+        payer: Option<&Addr>,
+    ) -> Result<(Vec<CosmosMsg>, AssetList, Event), FeeConfigError> {
+        let breakdown = match payer {
+            Some(payer) => self.fee_breakdown_for_payer(payer, assets, env)?,
+            None => self.fee_breakdown(assets, env)?,
+        };
+        let event = breakdown.fee_collected_event(payer);
+        Ok((
+            breakdown.transfer_msgs(env)?,
+            breakdown.assets_after_fees,
+            event,
+        ))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{Addr, Decimal, Event};
+    use cw_asset::{Asset, AssetInfo, AssetList};
+
+    use crate::FeeConfig;
+
+    #[test]
+    fn fee_msgs_and_event_from_assets() {
+        let env = mock_env();
+        let fee_config = FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(60).into()),
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(40).into(),
+                ),
+            ],
+            fee_rate_overrides: vec![(
+                AssetInfo::cw20(Addr::unchecked("token")),
+                Decimal::percent(20),
+            )],
+            fee_exemptions: vec![Addr::unchecked("exempt")],
+            ..Default::default()
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 50u128),
+        ]
+        .into();
+
+        let (msgs, assets_after_fees, event) = fee_config
+            .fee_msgs_and_event_from_assets(&assets, &env, Some(&Addr::unchecked("payer")))
+            .unwrap();
+        let (expected_msgs, expected_assets) =
+            fee_config.fee_msgs_from_assets(&assets, &env).unwrap();
+        assert_eq!(msgs, expected_msgs);
+        assert_eq!(assets_after_fees, expected_assets);
+        assert_eq!(
+            event,
+            Event::new("fee_collected")
+                .add_attribute("payer", "payer")
+                .add_attribute("fees", "native:uusdc:100,cw20:token:10")
+                .add_attribute("fee_rate", "native:uusdc:0.1,cw20:token:0.2")
+                .add_attribute("recipient", "addr1")
+                .add_attribute("amount", "native:uusdc:60,cw20:token:6")
+                .add_attribute("retained", "native:uusdc:40,cw20:token:4")
+        );
+
+        // Exempt payers don't pay any fees
+        let (msgs, assets_after_fees, event) = fee_config
+            .fee_msgs_and_event_from_assets(&assets, &env, Some(&Addr::unchecked("exempt")))
+            .unwrap();
+        assert!(msgs.is_empty());
+        assert_eq!(assets_after_fees, assets);
+        assert_eq!(
+            event,
+            Event::new("fee_collected")
+                .add_attribute("payer", "exempt")
+                .add_attribute("fees", "[]")
+                .add_attribute("fee_rate", "[]")
+                .add_attribute("retained", "[]")
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use cosmwasm_std::{Addr, CosmosMsg, Env, Order, Storage, Uint128};
+use cw_asset::{Asset, AssetInfo, AssetList};
+use cw_storage_plus::Map;
+
+use crate::{FeeConfig, FeeConfigError, FeeRecipient};
+
+/// A cw-storage-plus backed ledger that credits fees to the fee recipients
+/// instead of transferring them immediately. Recipients later claim their
+/// accrued balances.
+///
+/// Accounting is exact: the amounts that can't be credited to any recipient
+/// due to rounding are carried forward per asset and added to the next amount
+/// that is accrued, so that no fees are lost over time. The contract's own
+/// share and shares in assets that a recipient can't receive, see
+/// `FeeRecipient::can_receive`, are not credited to anyone and stay in the
+/// contract.
+///
+/// Balances are tracked per fee recipient and claimed with the same messages
+/// that `transfer_assets_msgs` would send. A contract recipient and a plain
+/// address recipient with the same address have separate balances, so fees
+/// accrued for a contract are always claimed with its hook message.
+pub struct FeeLedger<'a> {
+    balances: Map<'a, (&'a str, &'a AssetInfo), Uint128>,
+    remainders: Map<'a, &'a AssetInfo, Uint128>,
+}
+
+impl<'a> FeeLedger<'a> {
+    /// Creates a new `FeeLedger` that stores the recipient balances and the
+    /// carried rounding remainders under the given namespaces.
+    pub const fn new(balances_namespace: &'a str, remainders_namespace: &'a str) -> Self {
+        Self {
+            balances: Map::new(balances_namespace),
+            remainders: Map::new(remainders_namespace),
+        }
+    }
+
+    /// Credits an `AssetList` of assets that have already been taken as fees
+    /// to the fee recipients. This is the accruing counterpart of
+    /// `FeeConfig::transfer_assets_msgs`.
+    pub fn accrue(
+        &self,
+        storage: &mut dyn Storage,
+        config: &FeeConfig<Addr>,
+        fees: &AssetList,
+        env: &Env,
+    ) -> Result<(), FeeConfigError> {
+        if !config.charges_fees() {
+            return Ok(());
+        }
+        for fee in fees {
+            let remainder = self.remainder(storage, &fee.info)?;
+            let total = fee.amount.checked_add(remainder)?;
+            let shares = config.split_amount(total)?;
+            for ((recipient, _), share) in config.fee_recipients.iter().zip(&shares) {
+                // Like the contract's own share, shares in assets that the
+                // recipient can't receive stay in the contract
+                if recipient.address() == Some(&env.contract.address)
+                    || !recipient.can_receive(&fee.info)
+                    || share.is_zero()
+                {
+                    continue;
+                }
+                self.balances.update(
+                    storage,
+                    (&balance_key(recipient), &fee.info),
+                    |balance| -> Result<_, FeeConfigError> {
+                        Ok(balance.unwrap_or_default().checked_add(*share)?)
+                    },
+                )?;
+            }
+            let remainder = total - shares.iter().sum::<Uint128>();
+            if remainder.is_zero() {
+                self.remainders.remove(storage, &fee.info);
+            } else {
+                self.remainders.save(storage, &fee.info, &remainder)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculates the fee from the input assets like
+    /// `FeeConfig::fee_msgs_from_assets`, but credits it to the fee recipients
+    /// instead of transferring it. Returns the assets after fees.
+    pub fn accrue_fees_from_assets(
+        &self,
+        storage: &mut dyn Storage,
+        config: &FeeConfig<Addr>,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<AssetList, FeeConfigError> {
+        let breakdown = config.fee_breakdown(assets, env)?;
+        self.accrue(storage, config, &breakdown.fees, env)?;
+        Ok(breakdown.assets_after_fees)
+    }
+
+    /// Returns the balances accrued by a recipient.
+    pub fn balances(
+        &self,
+        storage: &dyn Storage,
+        recipient: &FeeRecipient<Addr>,
+    ) -> Result<AssetList, FeeConfigError> {
+        Ok(self
+            .balances
+            .prefix(&balance_key(recipient))
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (info, amount) = item?;
+                Ok(Asset::new(info, amount))
+            })
+            .collect::<Result<Vec<_>, FeeConfigError>>()?
+            .into())
+    }
+
+    /// Returns the rounding remainder of an asset that is carried forward to
+    /// the next accrual.
+    pub fn remainder(
+        &self,
+        storage: &dyn Storage,
+        info: &AssetInfo,
+    ) -> Result<Uint128, FeeConfigError> {
+        Ok(self.remainders.may_load(storage, info)?.unwrap_or_default())
+    }
+
+    /// Clears the balances accrued by a recipient and returns messages that
+    /// transfer them to the recipient.
+    pub fn claim_msgs(
+        &self,
+        storage: &mut dyn Storage,
+        recipient: &FeeRecipient<Addr>,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        let balances = self.balances(storage, recipient)?;
+        let key = balance_key(recipient);
+        for asset in &balances {
+            self.balances.remove(storage, (&key, &asset.info));
+        }
+        recipient.transfer_msgs(&balances, env)
+    }
+}
+
+/// Returns the key under which the balances of a recipient are stored. This
+/// is the recipient's `Display` form, except that contract recipients are
+/// prefixed to keep them apart from plain addresses.
+fn balance_key(recipient: &FeeRecipient<Addr>) -> String {
+    match recipient {
+        FeeRecipient::Contract { address, .. } => format!("contract:{address}"),
+        recipient => recipient.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{coin, to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Uint128, WasmMsg};
+    use cw_asset::{Asset, AssetInfo, AssetList};
+
+    use super::FeeLedger;
+    use crate::{Distribution, FeeConfig, FeeRecipient, Weight};
+
+    const LEDGER: FeeLedger = FeeLedger::new("fee_balances", "fee_remainders");
+
+    #[test]
+    fn accrue_carries_remainders_forward() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let fee_config = FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Weight::Shares(1)),
+                (Addr::unchecked("addr2").into(), Weight::Shares(1)),
+                (Addr::unchecked("addr3").into(), Weight::Shares(1)),
+            ],
+            distribution: Distribution::Independent,
+            ..Default::default()
+        };
+        let uusdc = AssetInfo::native("uusdc");
+
+        // 10 can't be split in thirds, so 1 is carried forward
+        let fees: AssetList = vec![Asset::native("uusdc", 10u128)].into();
+        LEDGER
+            .accrue(&mut deps.storage, &fee_config, &fees, &env)
+            .unwrap();
+        assert_eq!(
+            LEDGER
+                .balances(&deps.storage, &Addr::unchecked("addr1").into())
+                .unwrap(),
+            vec![Asset::native("uusdc", 3u128)].into()
+        );
+        assert_eq!(
+            LEDGER.remainder(&deps.storage, &uusdc).unwrap(),
+            Uint128::one()
+        );
+
+        // The remainder is included in the next split: 1 + 11 = 12
+        let fees: AssetList = vec![Asset::native("uusdc", 11u128)].into();
+        LEDGER
+            .accrue(&mut deps.storage, &fee_config, &fees, &env)
+            .unwrap();
+        for addr in ["addr1", "addr2", "addr3"] {
+            assert_eq!(
+                LEDGER
+                    .balances(&deps.storage, &Addr::unchecked(addr).into())
+                    .unwrap(),
+                vec![Asset::native("uusdc", 7u128)].into()
+            );
+        }
+        assert_eq!(
+            LEDGER.remainder(&deps.storage, &uusdc).unwrap(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn accrue_fees_from_assets_and_claim() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let fee_config = FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(50).into(),
+                ),
+            ],
+            ..Default::default()
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 100u128),
+        ]
+        .into();
+
+        let assets_after_fees = LEDGER
+            .accrue_fees_from_assets(&mut deps.storage, &fee_config, &assets, &env)
+            .unwrap();
+        assert_eq!(
+            assets_after_fees,
+            vec![
+                Asset::native("uusdc", 900u128),
+                Asset::cw20(Addr::unchecked("token"), 90u128)
+            ]
+            .into()
+        );
+        // The contract's own share is not credited
+        assert_eq!(
+            LEDGER
+                .balances(&deps.storage, &env.contract.address.clone().into())
+                .unwrap(),
+            AssetList::new()
+        );
+
+        let msgs = LEDGER
+            .claim_msgs(&mut deps.storage, &Addr::unchecked("addr1").into(), &env)
+            .unwrap();
+        assert_eq!(msgs.len(), 2);
+        assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr1".to_string(),
+            amount: vec![coin(50, "uusdc")],
+        })));
+
+        // Balances are cleared after claiming
+        assert_eq!(
+            LEDGER
+                .balances(&deps.storage, &Addr::unchecked("addr1").into())
+                .unwrap(),
+            AssetList::new()
+        );
+        assert!(LEDGER
+            .claim_msgs(&mut deps.storage, &Addr::unchecked("addr1").into(), &env)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn contract_balances_are_kept_apart_from_address_balances() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let contract = FeeRecipient::Contract {
+            address: Addr::unchecked("staking"),
+            msg: to_json_binary(&"distribute").unwrap(),
+        };
+        let fee_config = FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![(contract.clone(), Decimal::one().into())],
+            ..Default::default()
+        };
+        let fees: AssetList = vec![Asset::native("uusdc", 100u128)].into();
+        LEDGER
+            .accrue(&mut deps.storage, &fee_config, &fees, &env)
+            .unwrap();
+
+        // The contract's fees can't be claimed as a plain transfer
+        let address = Addr::unchecked("staking").into();
+        assert_eq!(
+            LEDGER.balances(&deps.storage, &address).unwrap(),
+            AssetList::new()
+        );
+        assert!(LEDGER
+            .claim_msgs(&mut deps.storage, &address, &env)
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            LEDGER
+                .claim_msgs(&mut deps.storage, &contract, &env)
+                .unwrap(),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&"distribute").unwrap(),
+                funds: vec![coin(100, "uusdc")],
+            })]
+        );
+    }
+
+    #[cfg(feature = "ibc")]
+    #[test]
+    fn accrue_retains_assets_recipients_cant_receive() {
+        use cosmwasm_std::{IbcMsg, IbcTimeout};
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let ibc = FeeRecipient::Ibc {
+            channel_id: "channel-0".to_string(),
+            address: "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t".to_string(),
+            timeout_seconds: 600,
+        };
+        let fee_config = FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (ibc.clone(), Decimal::percent(50).into()),
+            ],
+            ..Default::default()
+        };
+        let fees: AssetList = vec![
+            Asset::native("uusdc", 100u128),
+            Asset::cw20(Addr::unchecked("token"), 100u128),
+        ]
+        .into();
+        LEDGER
+            .accrue(&mut deps.storage, &fee_config, &fees, &env)
+            .unwrap();
+
+        // The IBC recipient's cw20 share is not credited, and is not carried
+        // forward either
+        assert_eq!(
+            LEDGER.balances(&deps.storage, &ibc).unwrap(),
+            vec![Asset::native("uusdc", 50u128)].into()
+        );
+        assert_eq!(
+            LEDGER
+                .remainder(&deps.storage, &AssetInfo::cw20(Addr::unchecked("token")))
+                .unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            LEDGER.claim_msgs(&mut deps.storage, &ibc, &env).unwrap(),
+            vec![CosmosMsg::Ibc(IbcMsg::Transfer {
+                channel_id: "channel-0".to_string(),
+                to_address: "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t".to_string(),
+                amount: coin(50, "uusdc"),
+                timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(600)),
+            })]
+        );
+    }
+}
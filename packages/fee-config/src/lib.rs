@@ -1,106 +1,762 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Coins, CosmosMsg, Decimal, Deps, Env, StdError, StdResult};
+use cosmwasm_std::{
+    Addr, CanonicalAddr, Coin, Coins, CosmosMsg, Decimal, Deps, Env, Uint128, Uint256,
+};
 use cw_address_like::AddressLike;
-use cw_asset::{Asset, AssetList};
+use cw_asset::{Asset, AssetInfo, AssetInfoBase, AssetList};
+
+use crate::recipient::RecipientId;
+
+mod bounds;
+mod breakdown;
+mod error;
+mod event;
+#[cfg(feature = "storage")]
+mod ledger;
+mod limits;
+mod msg;
+mod recipient;
+mod rounding;
+mod schedule;
+#[cfg(feature = "storage")]
+mod storage;
+mod tiers;
+mod update;
+mod weight;
+
+pub use bounds::FeeBounds;
+pub use breakdown::FeeBreakdown;
+pub use error::FeeConfigError;
+pub use event::FEE_COLLECTED_EVENT;
+#[cfg(feature = "storage")]
+pub use ledger::FeeLedger;
+pub use limits::FeeConfigLimits;
+#[cfg(feature = "storage")]
+pub use msg::{
+    execute_add_fee_recipient, execute_fee_config_msg, execute_remove_fee_recipient,
+    execute_update_fee_config, query_fee_config, query_fee_config_msg, query_recipient_weight,
+    query_simulate_fee,
+};
+pub use msg::{FeeConfigExecuteMsg, FeeConfigQueryMsg};
+pub use recipient::FeeRecipient;
+pub use rounding::Rounding;
+pub use schedule::FeeSchedule;
+#[cfg(feature = "storage")]
+pub use storage::FeeConfigItem;
+pub use tiers::{FeeTier, FeeTiers, TierMode};
+pub use update::FeeConfigUpdate;
+pub use weight::Weight;
 
 // Re-exports for convenience
 pub use cw_address_like;
 pub use cw_asset;
 
 #[cw_serde]
-#[derive(Default)]
+// Without an explicit bound, serde requires `T: Default` because of the
+// `#[serde(default)]` fields, which would make `FeeConfig<Addr>` impossible to
+// deserialize.
+#[serde(bound(deserialize = "T: serde::Deserialize<'de>"))]
 /// A struct that contains a fee configuration (fee rate and recipients).
 pub struct FeeConfig<T: AddressLike> {
     /// The fraction of the tokens that are taken as a fee.
     pub fee_rate: Decimal,
-    /// The addresses of the recipients of the fee. Each address in the vec is
-    /// paired with a Decimal, which represents the percentage of the fee
-    /// that should be sent to that address. The sum of all decimals must be
-    /// 1.
-    pub fee_recipients: Vec<(T, Decimal)>,
+    /// The recipients of the fee. Each recipient in the vec is paired with a
+    /// `Weight`, which represents the part of the fee that should be sent to
+    /// that recipient. Either all weights are percentages that sum to 1, or
+    /// all weights are shares.
+    pub fee_recipients: Vec<(FeeRecipient<T>, Weight)>,
+    /// How the fee is split between the fee recipients. Defaults to
+    /// `Distribution::Independent`.
+    #[serde(default)]
+    pub distribution: Distribution,
+    /// How fractional amounts are rounded, both when calculating the fee and
+    /// when splitting it between the fee recipients. Defaults to
+    /// `Rounding::Floor`.
+    #[serde(default)]
+    pub rounding: Rounding,
+    /// Optional minimum and maximum fee amounts per asset. Assets that are not
+    /// listed here are not bounded.
+    #[serde(default)]
+    pub fee_bounds: Vec<(AssetInfoBase<T>, FeeBounds)>,
+    /// Optional tiered fee rates per asset. For the assets listed here, the
+    /// tiers are used instead of `fee_rate`.
+    #[serde(default)]
+    pub fee_tiers: Vec<(AssetInfoBase<T>, FeeTiers)>,
+    /// Optional fee rates per asset that are used instead of `fee_rate`. A rate
+    /// of zero exempts the asset from fees, so the asset can't have a minimum
+    /// fee in `fee_bounds` as well.
+    #[serde(default)]
+    pub fee_rate_overrides: Vec<(AssetInfoBase<T>, Decimal)>,
+    /// Addresses that don't pay any fees when passed as the payer to the
+    /// `*_for_payer` functions.
+    #[serde(default)]
+    pub fee_exemptions: Vec<T>,
+}
+
+// Implemented manually because deriving `Default` would require `T: Default`,
+// which `Addr` does not implement.
+impl<T: AddressLike> Default for FeeConfig<T> {
+    fn default() -> Self {
+        Self {
+            fee_rate: Decimal::zero(),
+            fee_recipients: vec![],
+            distribution: Distribution::default(),
+            rounding: Rounding::default(),
+            fee_bounds: vec![],
+            fee_tiers: vec![],
+            fee_rate_overrides: vec![],
+            fee_exemptions: vec![],
+        }
+    }
+}
+
+impl<T: AddressLike> FeeConfig<T> {
+    /// Returns true if the config may take a non-zero fee, either because the
+    /// fee rate is non-zero, because some asset has a non-zero rate override or
+    /// fee tier, or because some asset has a minimum fee.
+    pub fn charges_fees(&self) -> bool {
+        !self.fee_rate.is_zero()
+            || self
+                .fee_rate_overrides
+                .iter()
+                .any(|(_, rate)| !rate.is_zero())
+            || self.fee_tiers.iter().any(|(_, tiers)| tiers.charges_fees())
+            || self
+                .fee_bounds
+                .iter()
+                .any(|(_, bounds)| bounds.min.is_some_and(|min| !min.is_zero()))
+    }
+
+    /// Returns the weight of each fee recipient as the fraction
+    /// `numerators[i] / denominator`. Share weights are normalized by the total
+    /// number of shares, percentages use the precision of `Decimal`. If the
+    /// kinds of weights are mixed, which `check` rejects, shares count as zero.
+    pub(crate) fn weight_fractions(&self) -> (Vec<Uint128>, Uint128) {
+        let shares = self
+            .fee_recipients
+            .iter()
+            .map(|(_, weight)| match weight {
+                Weight::Shares(shares) => Some(Uint128::from(*shares)),
+                Weight::Percent(_) => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        match shares {
+            Some(shares) if !shares.is_empty() => {
+                let total = shares.iter().sum();
+                (shares, total)
+            }
+            _ => (
+                self.fee_recipients
+                    .iter()
+                    .map(|(_, weight)| match weight {
+                        Weight::Shares(_) => Uint128::zero(),
+                        Weight::Percent(percent) => percent.atomics(),
+                    })
+                    .collect(),
+                Decimal::one().atomics(),
+            ),
+        }
+    }
+}
+
+/// Determines how strictly a fee config is validated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// The recipient weights must sum to 100% only if the config charges
+    /// fees, see `FeeConfig::charges_fees`.
+    #[default]
+    Standard,
+    /// The recipient weights must sum to 100% whenever there are recipients,
+    /// even if the config doesn't charge any fees yet. This catches configs
+    /// that would become invalid as soon as the fee rate is raised.
+    Strict,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// Determines how an amount is split between the fee recipients.
+pub enum Distribution {
+    /// Each recipient's share is rounded independently according to the
+    /// configured `Rounding`. Whatever is left over due to rounding is not
+    /// sent to anyone and stays in the contract. Shares that are rounded up
+    /// are capped at what is left of the amount, so recipients listed last
+    /// may come up short, see `Rounding::Ceil`.
+    #[default]
+    Independent,
+    /// Each recipient's share is rounded down, after which the leftover units
+    /// are handed out one at a time to the recipients with the largest
+    /// fractional remainders. Ties go to the recipient listed first. The
+    /// shares always sum to the full amount.
+    LargestRemainder,
+    /// Each recipient's share is rounded according to the configured
+    /// `Rounding`, except for the last recipient who receives whatever is
+    /// left. The shares always sum to the full amount.
+    LastRecipient,
 }
 
 impl FeeConfig<String> {
     /// Creates a new `FeeConfig<String>`
     pub fn new(fee_rate: Decimal, fee_recipients: &[(String, Decimal)]) -> Self {
+        Self::with_weights(
+            fee_rate,
+            fee_recipients
+                .iter()
+                .map(|(addr, percent)| (addr.clone().into(), Weight::Percent(*percent)))
+                .collect(),
+        )
+    }
+
+    /// Creates a new `FeeConfig<String>` whose fee is split between the
+    /// recipients pro rata to their number of shares.
+    pub fn new_with_shares(fee_rate: Decimal, fee_recipients: &[(String, u32)]) -> Self {
+        Self::with_weights(
+            fee_rate,
+            fee_recipients
+                .iter()
+                .map(|(addr, shares)| (addr.clone().into(), Weight::Shares(*shares)))
+                .collect(),
+        )
+    }
+
+    fn with_weights(
+        fee_rate: Decimal,
+        fee_recipients: Vec<(FeeRecipient<String>, Weight)>,
+    ) -> Self {
         Self {
             fee_rate,
-            fee_recipients: fee_recipients.to_vec(),
+            fee_recipients,
+            distribution: Distribution::default(),
+            rounding: Rounding::default(),
+            fee_bounds: vec![],
+            fee_tiers: vec![],
+            fee_rate_overrides: vec![],
+            fee_exemptions: vec![],
         }
     }
 
     /// Validates the fee config and returns a `FeeConfig<Addr>`.
-    pub fn check(&self, deps: &Deps) -> StdResult<FeeConfig<Addr>> {
-        // Fee rate must be between 0 and 100%
-        if self.fee_rate > Decimal::one() {
-            return Err(StdError::generic_err("Fee rate can't be higher than 100%"));
-        }
-        // If fee rate is not zero, then there must be some fee recipients and their
-        // weights must sum to 100%
-        if !self.fee_rate.is_zero()
-            && self.fee_recipients.iter().map(|(_, p)| p).sum::<Decimal>() != Decimal::one()
+    pub fn check(&self, deps: &Deps) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        self.check_with_mode(deps, ValidationMode::Standard)
+    }
+
+    /// Validates the fee config like `check`, but also requires the recipient
+    /// weights to sum to 100% when the config doesn't charge any fees yet. See
+    /// `ValidationMode::Strict`.
+    pub fn check_strict(&self, deps: &Deps) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        self.check_with_mode(deps, ValidationMode::Strict)
+    }
+
+    /// Validates the fee config like `check` and additionally makes sure that
+    /// it stays within the specified limits.
+    pub fn check_with_limits(
+        &self,
+        deps: &Deps,
+        limits: &FeeConfigLimits,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        let checked = self.check(deps)?;
+        limits.validate(self)?;
+        Ok(checked)
+    }
+
+    /// Validates the fee config and returns every problem that was found
+    /// instead of stopping at the first one. An empty vec means the config is
+    /// valid. The errors are listed in the order in which `check` would
+    /// encounter them.
+    pub fn validate(&self, deps: &Deps, mode: ValidationMode) -> Vec<FeeConfigError> {
+        self.validate_inner(deps, mode).1
+    }
+
+    fn check_with_mode(
+        &self,
+        deps: &Deps,
+        mode: ValidationMode,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        let (checked, mut errors) = self.validate_inner(deps, mode);
+        if errors.is_empty() {
+            Ok(checked)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Validates the fee config, collecting all errors. Entries that fail
+    /// validation are left out of the returned `FeeConfig<Addr>`, which is
+    /// therefore only meaningful if no errors were found.
+    fn validate_inner(
+        &self,
+        deps: &Deps,
+        mode: ValidationMode,
+    ) -> (FeeConfig<Addr>, Vec<FeeConfigError>) {
+        let mut errors = vec![];
+        // Fee rate and rate overrides must be between 0 and 100%
+        for rate in std::iter::once(&self.fee_rate)
+            .chain(self.fee_rate_overrides.iter().map(|(_, rate)| rate))
+            .filter(|rate| **rate > Decimal::one())
         {
-            return Err(StdError::generic_err(
-                "Sum of fee recipient percentages must be 100%",
-            ));
+            errors.push(FeeConfigError::RateTooHigh { rate: *rate });
         }
-        // Fee recipients should not contain zero weights
-        if self.fee_recipients.iter().any(|(_, p)| p.is_zero()) {
-            return Err(StdError::generic_err(
-                "Fee recipient percentages must be greater than zero",
-            ));
+        // All fee recipients must use the same kind of weight
+        let shares = self
+            .fee_recipients
+            .iter()
+            .filter(|(_, weight)| matches!(weight, Weight::Shares(_)))
+            .count();
+        if shares > 0 && shares < self.fee_recipients.len() {
+            errors.push(FeeConfigError::MixedWeights);
         }
-        Ok(FeeConfig {
-            fee_rate: self.fee_rate,
-            fee_recipients: self
+        // If fees can be charged, then there must be some fee recipients and their
+        // percentages must sum to 100%. In strict mode this is also required when
+        // there are recipients but no fees are charged yet. Shares are always
+        // normalized, so they only need to be non-zero.
+        let weights_required = match mode {
+            ValidationMode::Standard => self.charges_fees(),
+            ValidationMode::Strict => self.charges_fees() || !self.fee_recipients.is_empty(),
+        };
+        if weights_required && shares == 0 {
+            let sum = self
                 .fee_recipients
                 .iter()
-                .map(|(addr, percentage)| Ok((deps.api.addr_validate(addr)?, *percentage)))
-                .collect::<StdResult<Vec<_>>>()?,
-        })
+                .filter_map(|(_, weight)| match weight {
+                    Weight::Percent(percent) => Some(*percent),
+                    Weight::Shares(_) => None,
+                })
+                .sum::<Decimal>();
+            if sum != Decimal::one() {
+                errors.push(FeeConfigError::WeightsDoNotSumToOne { sum });
+            }
+        }
+        // Fee recipients should not contain zero weights
+        for (recipient, _) in self.fee_recipients.iter().filter(|(_, p)| p.is_zero()) {
+            errors.push(FeeConfigError::ZeroWeight {
+                recipient: recipient.to_string(),
+            });
+        }
+        // Each fee recipient may only be listed once. Addresses are compared in
+        // their canonical form so that differences in case are caught as well.
+        // Invalid addresses are reported below.
+        let ids = self
+            .fee_recipients
+            .iter()
+            .map(|(recipient, _)| recipient.id(deps).ok())
+            .collect::<Vec<_>>();
+        for (i, id) in ids.iter().enumerate() {
+            if id.is_some() && ids[..i].contains(id) {
+                errors.push(FeeConfigError::DuplicateRecipient {
+                    recipient: self.fee_recipients[i].0.to_string(),
+                });
+            }
+        }
+        // Fee bounds and tiers must be valid and at most one entry per asset is
+        // allowed
+        for (_, bounds) in &self.fee_bounds {
+            errors.extend(bounds.validate().err());
+        }
+        for (_, tiers) in &self.fee_tiers {
+            errors.extend(tiers.validate().err());
+        }
+        let fee_bounds = check_asset_entries(&self.fee_bounds, deps, "fee bounds", &mut errors);
+        let fee_tiers = check_asset_entries(&self.fee_tiers, deps, "fee tiers", &mut errors);
+        let fee_rate_overrides = check_asset_entries(
+            &self.fee_rate_overrides,
+            deps,
+            "fee rate override",
+            &mut errors,
+        );
+        // An asset can't have both tiers and a rate override
+        for (info, _) in fee_rate_overrides
+            .iter()
+            .filter(|(info, _)| fee_tiers.iter().any(|(other, _)| other == info))
+        {
+            errors.push(FeeConfigError::TiersAndRateOverride {
+                asset: info.clone(),
+            });
+        }
+        // An asset that is exempted with a zero rate override can't be charged a
+        // minimum fee
+        for (info, _) in fee_rate_overrides.iter().filter(|(info, rate)| {
+            rate.is_zero()
+                && fee_bounds.iter().any(|(other, bounds)| {
+                    other == info && bounds.min.is_some_and(|min| !min.is_zero())
+                })
+        }) {
+            errors.push(FeeConfigError::MinFeeForExemptAsset {
+                asset: info.clone(),
+            });
+        }
+        // Assets that the config explicitly charges fees for must be receivable
+        // by every recipient, e.g. IBC recipients can't receive cw20 fees.
+        // Assets that are exempted or only capped are fine, and the shares of
+        // other assets that a recipient can't receive are retained.
+        let charged_assets = fee_bounds
+            .iter()
+            .filter(|(_, bounds)| bounds.min.is_some_and(|min| !min.is_zero()))
+            .map(|(info, _)| info)
+            .chain(
+                fee_tiers
+                    .iter()
+                    .filter(|(_, tiers)| tiers.charges_fees())
+                    .map(|(info, _)| info),
+            )
+            .chain(
+                fee_rate_overrides
+                    .iter()
+                    .filter(|(_, rate)| !rate.is_zero())
+                    .map(|(info, _)| info),
+            );
+        for info in charged_assets {
+            for (recipient, _) in &self.fee_recipients {
+                if !recipient.can_receive(info) {
+                    errors.push(FeeConfigError::UnsupportedAsset {
+                        recipient: recipient.to_string(),
+                        asset: info.clone(),
+                    });
+                }
+            }
+        }
+        let fee_recipients = self
+            .fee_recipients
+            .iter()
+            .filter_map(|(recipient, weight)| {
+                recipient
+                    .check(deps)
+                    .map_err(|e| errors.push(e))
+                    .ok()
+                    .map(|recipient| (recipient, *weight))
+            })
+            .collect();
+        let fee_exemptions = self
+            .fee_exemptions
+            .iter()
+            .filter_map(|addr| validate_addr(addr, deps).map_err(|e| errors.push(e)).ok())
+            .collect();
+        let checked = FeeConfig {
+            fee_rate: self.fee_rate,
+            fee_recipients,
+            distribution: self.distribution.clone(),
+            rounding: self.rounding,
+            fee_bounds,
+            fee_tiers,
+            fee_rate_overrides,
+            fee_exemptions,
+        };
+        (checked, errors)
+    }
+
+    /// Validates the fee config like `check`, but instead of rejecting fee
+    /// recipients that are listed more than once, merges them into a single
+    /// entry whose weight is the sum of the duplicates' weights. The merged
+    /// entry takes the position of the first occurrence. Recipient addresses
+    /// are normalized before they are compared and validated. Only identical
+    /// recipients are merged: an address that is listed as different kinds of
+    /// recipients, e.g. both as a plain address and as a contract with a hook
+    /// message, is rejected as a duplicate.
+    pub fn check_merging_duplicates(&self, deps: &Deps) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        let mut merged: Vec<(RecipientId, FeeRecipient<String>, Weight)> = vec![];
+        for (recipient, weight) in &self.fee_recipients {
+            let id = recipient.id(deps)?;
+            let normalized = match &id {
+                RecipientId::Local(canonical) => recipient.try_map_address(|_| {
+                    Ok::<_, FeeConfigError>(deps.api.addr_humanize(canonical)?.into_string())
+                })?,
+                RecipientId::Other(_) => recipient.clone(),
+            };
+            match merged.iter_mut().find(|(other, _, _)| *other == id) {
+                Some((_, other, total)) if *other == normalized => {
+                    *total = total.checked_add(*weight)?
+                }
+                Some(_) => {
+                    return Err(FeeConfigError::DuplicateRecipient {
+                        recipient: recipient.to_string(),
+                    })
+                }
+                None => merged.push((id, normalized, *weight)),
+            }
+        }
+        FeeConfig {
+            fee_recipients: merged
+                .into_iter()
+                .map(|(_, recipient, weight)| (recipient, weight))
+                .collect(),
+            ..self.clone()
+        }
+        .check(deps)
     }
 }
 
 impl FeeConfig<Addr> {
-    /// Creates messages to transfer an `AssetList` of assets to the fee
-    /// recipients.
-    pub fn transfer_assets_msgs(&self, assets: &AssetList, env: &Env) -> StdResult<Vec<CosmosMsg>> {
-        if self.fee_rate.is_zero() {
-            return Ok(vec![]);
+    /// Returns the fee bounds of the specified asset, if any.
+    pub fn fee_bounds_for(&self, info: &AssetInfo) -> Option<&FeeBounds> {
+        self.fee_bounds
+            .iter()
+            .find(|(asset_info, _)| asset_info == info)
+            .map(|(_, bounds)| bounds)
+    }
+
+    /// Returns the fee tiers of the specified asset, if any.
+    pub fn fee_tiers_for(&self, info: &AssetInfo) -> Option<&FeeTiers> {
+        self.fee_tiers
+            .iter()
+            .find(|(asset_info, _)| asset_info == info)
+            .map(|(_, tiers)| tiers)
+    }
+
+    /// Returns the fee rate of the specified asset, which is its rate override
+    /// if it has one and `fee_rate` otherwise. Fee tiers are not taken into
+    /// account.
+    pub fn fee_rate_for(&self, info: &AssetInfo) -> Decimal {
+        self.fee_rate_overrides
+            .iter()
+            .find(|(asset_info, _)| asset_info == info)
+            .map_or(self.fee_rate, |(_, rate)| *rate)
+    }
+
+    /// Calculates the fee that is taken from `asset`, using the asset's
+    /// `FeeTiers` if it has any and its fee rate from `fee_rate_for`
+    /// otherwise. The fee is rounded according to the configured `Rounding`
+    /// and clamped to the asset's `FeeBounds`.
+    pub fn fee_amount(&self, asset: &Asset) -> Result<Uint128, FeeConfigError> {
+        let mut fee = match self.fee_tiers_for(&asset.info) {
+            Some(tiers) => tiers.fee(asset.amount, self.rounding)?,
+            None => self
+                .rounding
+                .mul_decimal(asset.amount, self.fee_rate_for(&asset.info))?,
+        };
+        if let Some(bounds) = self.fee_bounds_for(&asset.info) {
+            fee = bounds.apply(asset.amount, fee)?;
+        }
+        Ok(fee.min(asset.amount))
+    }
+
+    /// Splits `amount` between the fee recipients according to their weights
+    /// and the configured `Distribution` and `Rounding`. The returned amounts
+    /// are in the same order as `fee_recipients` and never sum to more than
+    /// `amount`.
+    pub fn split_amount(&self, amount: Uint128) -> Result<Vec<Uint128>, FeeConfigError> {
+        let (numerators, denominator) = self.weight_fractions();
+        let precision = Uint256::from(denominator);
+        if precision.is_zero() {
+            return Ok(vec![Uint128::zero(); numerators.len()]);
+        }
+        let products = numerators
+            .iter()
+            .map(|numerator| Uint256::from(amount) * Uint256::from(*numerator))
+            .collect::<Vec<_>>();
+
+        // The total that should be handed out. This equals `amount` when the
+        // weights sum to one, which `check` enforces for percentages and which
+        // always holds for shares.
+        let total: Uint128 =
+            (products.iter().fold(Uint256::zero(), |acc, p| acc + *p) / precision).try_into()?;
+        let total = total.min(amount);
+
+        if self.distribution == Distribution::LargestRemainder {
+            // The floored shares are capped like below in case the weights sum
+            // to more than one, so whatever is left of the total is the leftover
+            let mut leftover = total;
+            let mut shares = products
+                .iter()
+                .map(|product| {
+                    let share: Uint128 = (*product / precision).try_into()?;
+                    let share = share.min(leftover);
+                    leftover -= share;
+                    Ok(share)
+                })
+                .collect::<Result<Vec<_>, FeeConfigError>>()?;
+            let mut order = (0..shares.len()).collect::<Vec<_>>();
+            // Stable sort, so ties are resolved in favour of the first recipient
+            order.sort_by(|a, b| (products[*b] % precision).cmp(&(products[*a] % precision)));
+            for i in order.into_iter().take(leftover.u128() as usize) {
+                shares[i] += Uint128::one();
+            }
+            return Ok(shares);
+        }
+
+        // Rounding up may lead to shares summing to more than the total, so each
+        // share is capped at what is still remaining.
+        let mut remaining = total;
+        let mut shares = products
+            .iter()
+            .map(|product| {
+                let share: Uint128 = self.rounding.div(*product, precision)?.try_into()?;
+                let share = share.min(remaining);
+                remaining -= share;
+                Ok(share)
+            })
+            .collect::<Result<Vec<_>, FeeConfigError>>()?;
+        if self.distribution == Distribution::LastRecipient {
+            if let Some(last) = shares.last_mut() {
+                *last += remaining;
+            }
         }
-        Ok(self
+
+        Ok(shares)
+    }
+
+    /// Calculates how an `AssetList` of assets that have already been taken as
+    /// fees is split between the fee recipients, without creating any
+    /// messages. The `assets_after_fees` field of the returned breakdown is
+    /// empty, since all of the assets are treated as fees. Shares in assets
+    /// that a recipient can't receive, see `FeeRecipient::can_receive`, are
+    /// retained by the contract.
+    pub fn transfer_breakdown(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<FeeBreakdown, FeeConfigError> {
+        if !self.charges_fees() {
+            return Ok(FeeBreakdown {
+                fees: assets.clone(),
+                retained: assets.clone(),
+                ..Default::default()
+            });
+        }
+        let shares = assets
+            .into_iter()
+            .map(|asset| self.split_amount(asset.amount))
+            .collect::<Result<Vec<_>, FeeConfigError>>()?;
+
+        let recipients = self
             .fee_recipients
             .iter()
+            .enumerate()
             // Filter out the contract address because it's unnecessary to send fees to ourselves
-            .filter(|(addr, _)| addr != env.contract.address)
-            .map(|(addr, percentage)| {
+            .filter(|(_, (recipient, _))| recipient.address() != Some(&env.contract.address))
+            .map(|(i, (recipient, _))| {
                 let assets: AssetList = assets
                     .into_iter()
-                    .map(|asset| Asset::new(asset.info.clone(), asset.amount * *percentage))
-                    .filter(|asset| !asset.amount.is_zero())
+                    .zip(&shares)
+                    .map(|(asset, shares)| Asset::new(asset.info.clone(), shares[i]))
+                    .filter(|asset| !asset.amount.is_zero() && recipient.can_receive(&asset.info))
                     .collect::<Vec<_>>()
                     .into();
-                assets.transfer_msgs(addr).map_err(|e| {
-                    StdError::generic_err(format!(
-                        "Failed to create transfer messages for AssetList {}. Error: {}",
-                        assets, e
-                    ))
-                })
+                (recipient.clone(), assets)
+            })
+            .collect::<Vec<_>>();
+
+        let retained: AssetList = assets
+            .into_iter()
+            .zip(&shares)
+            .map(|(asset, shares)| {
+                let sent: Uint128 = self
+                    .fee_recipients
+                    .iter()
+                    .zip(shares)
+                    .filter(|((recipient, _), _)| {
+                        recipient.address() != Some(&env.contract.address)
+                            && recipient.can_receive(&asset.info)
+                    })
+                    .map(|(_, share)| *share)
+                    .sum();
+                Asset::new(asset.info.clone(), asset.amount - sent)
             })
-            .collect::<StdResult<Vec<_>>>()?
+            .filter(|asset| !asset.amount.is_zero())
+            .collect::<Vec<_>>()
+            .into();
+
+        Ok(FeeBreakdown {
+            fees: assets.clone(),
+            recipients,
+            retained,
+            assets_after_fees: AssetList::new(),
+        })
+    }
+
+    /// Calculates the fee from the input assets and how it is split between
+    /// the fee recipients, without creating any messages.
+    pub fn fee_breakdown(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<FeeBreakdown, FeeConfigError> {
+        // Take fee from input assets and filter out zero amounts
+        let fees: AssetList = assets
+            .into_iter()
+            .map(|asset| Ok(Asset::new(asset.info.clone(), self.fee_amount(asset)?)))
+            .collect::<Result<Vec<_>, FeeConfigError>>()?
             .into_iter()
-            .flatten()
-            .collect())
+            .filter(|asset| !asset.amount.is_zero())
+            .collect::<Vec<_>>()
+            .into();
+
+        let mut assets_after_fees = assets.clone();
+        assets_after_fees
+            .deduct_many(&fees)
+            .map_err(|source| FeeConfigError::DeductFees {
+                assets: assets.clone(),
+                source,
+            })?;
+
+        Ok(FeeBreakdown {
+            assets_after_fees,
+            ..self.transfer_breakdown(&fees, env)?
+        })
+    }
+
+    /// Returns true if `payer` is exempt from fees.
+    pub fn is_exempt(&self, payer: &Addr) -> bool {
+        self.fee_exemptions.contains(payer)
+    }
+
+    /// Like `fee_breakdown`, but takes no fee at all if `payer` is exempt from
+    /// fees.
+    pub fn fee_breakdown_for_payer(
+        &self,
+        payer: &Addr,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<FeeBreakdown, FeeConfigError> {
+        if self.is_exempt(payer) {
+            return Ok(FeeBreakdown {
+                assets_after_fees: assets.clone(),
+                ..Default::default()
+            });
+        }
+        self.fee_breakdown(assets, env)
+    }
+
+    /// Creates messages to transfer an `AssetList` of assets to the fee
+    /// recipients.
+    pub fn transfer_assets_msgs(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        self.transfer_breakdown(assets, env)?.transfer_msgs(env)
+    }
+
+    /// Creates messages to transfer an `AssetList` of assets to the fee
+    /// recipients and returns the part that stays in the contract.
+    ///
+    /// # Returns
+    /// * `Vec<CosmosMsg>` - The messages to send the assets to the fee
+    ///   recipients.
+    /// * `AssetList` - The assets retained by the contract, i.e. the contract's
+    ///   own share plus any amount left over due to rounding.
+    pub fn transfer_assets_msgs_with_retained(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, AssetList), FeeConfigError> {
+        let breakdown = self.transfer_breakdown(assets, env)?;
+        Ok((breakdown.transfer_msgs(env)?, breakdown.retained))
     }
 
     /// Creates messages to transfer a single `Coin` to the fee recipients.
-    pub fn transfer_coin_msgs(&self, coin: &Coin, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+    pub fn transfer_coin_msgs(
+        &self,
+        coin: &Coin,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
         let assets = AssetList::from(vec![coin.clone()].as_slice());
         self.transfer_assets_msgs(&assets, env)
     }
 
     /// Creates messages to transfer a `Coins` to the fee recipients.
-    pub fn transfer_coins_msgs(&self, coins: &Coins, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+    pub fn transfer_coins_msgs(
+        &self,
+        coins: &Coins,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
         let assets: AssetList = coins
             .into_iter()
             .map(Into::into)
@@ -123,25 +779,59 @@ impl FeeConfig<Addr> {
         &self,
         assets: &AssetList,
         env: &Env,
-    ) -> StdResult<(Vec<CosmosMsg>, AssetList)> {
-        // Take fee from input assets and filter out zero amounts
-        let fees: AssetList = assets
-            .into_iter()
-            .map(|asset| Asset::new(asset.info.clone(), asset.amount * self.fee_rate))
-            .filter(|asset| !asset.amount.is_zero())
-            .collect::<Vec<_>>()
-            .into();
+    ) -> Result<(Vec<CosmosMsg>, AssetList), FeeConfigError> {
+        let (fee_msgs, assets_after_fees, _) =
+            self.fee_msgs_from_assets_with_retained(assets, env)?;
+        Ok((fee_msgs, assets_after_fees))
+    }
 
-        let mut assets_after_fees = assets.clone();
-        assets_after_fees.deduct_many(&fees).map_err(|e| {
-            StdError::generic_err(format!(
-                "Failed to deduct fees from AssetList {}. Error: {}",
-                assets, e
-            ))
-        })?;
+    /// Calculates the fee from the input assets and returns messages to send
+    /// them to the fee recipients, along with the part of the fee that stays
+    /// in the contract.
+    ///
+    /// # Arguments
+    /// * `assets` - The assets to take the fee from.
+    ///
+    /// # Returns
+    /// * `Vec<CosmosMsg>` - The messages to send the fees to the fee
+    ///   recipients.
+    /// * `AssetList` - The assets after the fee has been taken.
+    /// * `AssetList` - The fees retained by the contract, i.e. the contract's
+    ///   own share plus any amount left over due to rounding.
+    pub fn fee_msgs_from_assets_with_retained(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, AssetList, AssetList), FeeConfigError> {
+        let breakdown = self.fee_breakdown(assets, env)?;
 
         // Send fee to fee recipients
-        Ok((self.transfer_assets_msgs(&fees, env)?, assets_after_fees))
+        Ok((
+            breakdown.transfer_msgs(env)?,
+            breakdown.assets_after_fees,
+            breakdown.retained,
+        ))
+    }
+
+    /// Like `fee_msgs_from_assets`, but takes no fee at all if `payer` is
+    /// exempt from fees.
+    ///
+    /// # Arguments
+    /// * `payer` - The address paying the fee.
+    /// * `assets` - The assets to take the fee from.
+    ///
+    /// # Returns
+    /// * `Vec<CosmosMsg>` - The messages to send the fees to the fee
+    ///   recipients.
+    /// * `AssetList` - The assets after the fee has been taken.
+    pub fn fee_msgs_from_assets_for_payer(
+        &self,
+        payer: &Addr,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, AssetList), FeeConfigError> {
+        let breakdown = self.fee_breakdown_for_payer(payer, assets, env)?;
+        Ok((breakdown.transfer_msgs(env)?, breakdown.assets_after_fees))
     }
 
     /// Calculates the fee from the input asset and returns messages to send it
@@ -158,10 +848,15 @@ impl FeeConfig<Addr> {
         &self,
         asset: Asset,
         env: &Env,
-    ) -> StdResult<(Vec<CosmosMsg>, Asset)> {
+    ) -> Result<(Vec<CosmosMsg>, Asset), FeeConfigError> {
         let (msgs, assets_after_fee) =
-            self.fee_msgs_from_assets(&AssetList::from(vec![asset]), env)?;
-        Ok((msgs, assets_after_fee.to_vec()[0].clone()))
+            self.fee_msgs_from_assets(&AssetList::from(vec![asset.clone()]), env)?;
+        // The asset is missing from the list if the whole amount was taken
+        let amount = assets_after_fee
+            .find(&asset.info)
+            .map(|asset| asset.amount)
+            .unwrap_or_default();
+        Ok((msgs, Asset::new(asset.info, amount)))
     }
 
     /// Calculates the fee from the input coins and returns a message to send
@@ -177,29 +872,64 @@ impl FeeConfig<Addr> {
         &self,
         coins: &Coins,
         env: &Env,
-    ) -> StdResult<(Vec<CosmosMsg>, Coins)> {
+    ) -> Result<(Vec<CosmosMsg>, Coins), FeeConfigError> {
+        let (fee_msgs, coins_after_fees, _) = self.fee_msgs_from_coins_with_retained(coins, env)?;
+        Ok((fee_msgs, coins_after_fees))
+    }
+
+    /// Calculates the fee from the input coins and returns messages to send
+    /// them to the fee recipients, along with the part of the fee that stays
+    /// in the contract.
+    ///
+    /// # Arguments
+    /// * `coins` - The coins to take the fee from.
+    ///
+    /// # Returns
+    /// * `Vec<CosmosMsg>` - The messages to send the fees to the fee recipients.
+    /// * `Coins` - The coins after the fee has been taken.
+    /// * `Coins` - The fees retained by the contract, i.e. the contract's own
+    ///   share plus any amount left over due to rounding.
+    pub fn fee_msgs_from_coins_with_retained(
+        &self,
+        coins: &Coins,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, Coins, Coins), FeeConfigError> {
         let assets: AssetList = coins
             .into_iter()
             .map(Into::into)
             .collect::<Vec<Asset>>()
             .into();
 
-        let (fee_msgs, assets_after_fees) = self.fee_msgs_from_assets(&assets, env)?;
+        let (fee_msgs, assets_after_fees, retained) =
+            self.fee_msgs_from_assets_with_retained(&assets, env)?;
 
-        let coins_after_fees: Coins = assets_after_fees
-            .into_iter()
-            .map(|a| {
-                a.clone().try_into().map_err(|e| {
-                    StdError::generic_err(format!(
-                        "Failed to convert Asset {} to Coin. Error: {}",
-                        a, e
-                    ))
-                })
-            })
-            .collect::<StdResult<Vec<Coin>>>()?
-            .try_into()?;
+        Ok((
+            fee_msgs,
+            assets_to_coins(&assets_after_fees)?,
+            assets_to_coins(&retained)?,
+        ))
+    }
 
-        Ok((fee_msgs, coins_after_fees))
+    /// Like `fee_msgs_from_coins`, but takes no fee at all if `payer` is
+    /// exempt from fees.
+    ///
+    /// # Arguments
+    /// * `payer` - The address paying the fee.
+    /// * `coins` - The coins to take the fee from.
+    ///
+    /// # Returns
+    /// * `Vec<CosmosMsg>` - The messages to send the fees to the fee recipients.
+    /// * `Coins` - The coins after the fee has been taken.
+    pub fn fee_msgs_from_coins_for_payer(
+        &self,
+        payer: &Addr,
+        coins: &Coins,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, Coins), FeeConfigError> {
+        if self.is_exempt(payer) {
+            return Ok((vec![], coins.clone()));
+        }
+        self.fee_msgs_from_coins(coins, env)
     }
 
     /// Calculates the fee from the input coin and returns messages to send it
@@ -212,7 +942,11 @@ impl FeeConfig<Addr> {
     /// * `Vec<CosmosMsg>` - The messages to send the fees to the fee
     ///   recipients.
     /// * `Coin` - The asset after the fee has been taken.
-    pub fn fee_msgs_from_coin(&self, coin: Coin, env: &Env) -> StdResult<(Vec<CosmosMsg>, Coin)> {
+    pub fn fee_msgs_from_coin(
+        &self,
+        coin: Coin,
+        env: &Env,
+    ) -> Result<(Vec<CosmosMsg>, Coin), FeeConfigError> {
         let (msgs, coins_after_fee) =
             self.fee_msgs_from_coins(&Coins::try_from(vec![coin.clone()])?, env)?;
         Ok((
@@ -221,24 +955,235 @@ impl FeeConfig<Addr> {
         ))
     }
 
-    /// Returns the fee weight of the specified recipient address.
-    pub fn recipient_weight(&self, recipient: &Addr) -> Decimal {
-        self.fee_recipients
-            .iter()
-            .find(|(addr, _)| addr == recipient)
-            .map(|(_, weight)| *weight)
-            .unwrap_or_else(|| Decimal::zero())
+    /// Calculates the smallest gross amount of the specified asset from which
+    /// exactly `net` remains after the fee has been taken. With flat fee tiers
+    /// some net amounts can't be reached exactly, in which case the smallest
+    /// gross amount from which more than `net` remains is returned.
+    pub fn gross_up_amount(
+        &self,
+        info: &AssetInfo,
+        net: Uint128,
+    ) -> Result<Uint128, FeeConfigError> {
+        let net_after_fee = |gross: Uint128| -> Result<Uint128, FeeConfigError> {
+            Ok(gross - self.fee_amount(&Asset::new(info.clone(), gross))?)
+        };
+        if net.is_zero() {
+            return Ok(net);
+        }
+        let bounds = self.fee_bounds_for(info).cloned().unwrap_or_default();
+        let min_fee = bounds.min.unwrap_or_default();
+        let (rates, ranges) = match self.fee_tiers_for(info) {
+            Some(tiers) => (
+                tiers.tiers.iter().map(|tier| tier.rate).collect(),
+                tiers.monotonic_ranges(),
+            ),
+            None => (
+                vec![self.fee_rate_for(info)],
+                vec![(Uint128::zero(), Uint128::MAX)],
+            ),
+        };
+        if bounds.max.is_none() && rates.iter().all(|rate| *rate >= Decimal::one()) {
+            return Err(FeeConfigError::GrossUpWithFullFeeRate);
+        }
+
+        // Within each range the amount remaining after fees never decreases as
+        // the gross amount increases, and grows by at most one unit at a time.
+        // The smallest gross amount in a range with at least `net` remaining
+        // therefore has exactly `net` remaining, unless more than `net` already
+        // remains at the start of the range. Since the fee is at least the
+        // minimum fee, nothing below `net + min_fee` can qualify.
+        let mut inexact = None;
+        for (start, end) in ranges {
+            let mut low = start.max(net.saturating_add(min_fee));
+            let mut high = end;
+            if low > high || net_after_fee(high)? < net {
+                continue;
+            }
+            while low < high {
+                let mid = low + (high - low) / Uint128::new(2);
+                if net_after_fee(mid)? < net {
+                    low = mid + Uint128::one();
+                } else {
+                    high = mid;
+                }
+            }
+            if net_after_fee(low)? == net {
+                return Ok(low);
+            }
+            inexact.get_or_insert(low);
+        }
+        inexact.ok_or(FeeConfigError::GrossUpOverflow { net })
     }
-}
 
-impl From<FeeConfig<Addr>> for FeeConfig<String> {
-    fn from(value: FeeConfig<Addr>) -> Self {
-        Self {
+    /// Calculates the asset that must be supplied so that exactly `net`
+    /// remains after the fee has been taken.
+    pub fn gross_up_asset(&self, net: &Asset) -> Result<Asset, FeeConfigError> {
+        Ok(Asset::new(
+            net.info.clone(),
+            self.gross_up_amount(&net.info, net.amount)?,
+        ))
+    }
+
+    /// Calculates the assets that must be supplied so that exactly `net`
+    /// remains after the fees have been taken.
+    pub fn gross_up_assets(&self, net: &AssetList) -> Result<AssetList, FeeConfigError> {
+        Ok(net
+            .into_iter()
+            .map(|asset| self.gross_up_asset(asset))
+            .collect::<Result<Vec<_>, FeeConfigError>>()?
+            .into())
+    }
+
+    /// Calculates the coin that must be supplied so that exactly `net`
+    /// remains after the fee has been taken.
+    pub fn gross_up_coin(&self, net: &Coin) -> Result<Coin, FeeConfigError> {
+        Ok(Coin::new(
+            self.gross_up_amount(&AssetInfo::native(&net.denom), net.amount)?
+                .u128(),
+            net.denom.clone(),
+        ))
+    }
+
+    /// Calculates the coins that must be supplied so that exactly `net`
+    /// remains after the fees have been taken.
+    pub fn gross_up_coins(&self, net: &Coins) -> Result<Coins, FeeConfigError> {
+        Ok(net
+            .iter()
+            .map(|coin| self.gross_up_coin(coin))
+            .collect::<Result<Vec<_>, FeeConfigError>>()?
+            .try_into()?)
+    }
+
+    /// Returns the effective fraction of the fee that the specified recipient
+    /// address receives. For share weights this is the recipient's shares
+    /// divided by the total number of shares.
+    pub fn recipient_weight(&self, recipient: &Addr) -> Decimal {
+        self.fee_recipient_weight(&recipient.clone().into())
+    }
+
+    /// Returns the effective fraction of the fee that the specified fee
+    /// recipient receives, e.g. the burned share for `FeeRecipient::Burn`.
+    /// Recipients with an address are matched by their address.
+    pub fn fee_recipient_weight(&self, recipient: &FeeRecipient<Addr>) -> Decimal {
+        let (numerators, denominator) = self.weight_fractions();
+        // All weights are zero, which `check` rejects
+        if denominator.is_zero() {
+            return Decimal::zero();
+        }
+        self.fee_recipients
+            .iter()
+            .zip(numerators)
+            .find(|((other, _), _)| other.is_same(recipient))
+            .map(|(_, numerator)| Decimal::from_ratio(numerator, denominator))
+            .unwrap_or_default()
+    }
+}
+
+/// Validates an address, mapping the error into a `FeeConfigError`.
+fn validate_addr(address: &str, deps: &Deps) -> Result<Addr, FeeConfigError> {
+    deps.api
+        .addr_validate(address)
+        .map_err(|source| FeeConfigError::InvalidAddress {
+            address: address.to_string(),
+            source,
+        })
+}
+
+/// Converts an address into its canonical form, mapping the error into a
+/// `FeeConfigError`.
+fn canonicalize_addr(address: &str, deps: &Deps) -> Result<CanonicalAddr, FeeConfigError> {
+    deps.api
+        .addr_canonicalize(address)
+        .map_err(|source| FeeConfigError::InvalidAddress {
+            address: address.to_string(),
+            source,
+        })
+}
+
+/// Validates an `AssetInfoBase<String>`, mapping the error into a
+/// `FeeConfigError`.
+fn check_asset_info(
+    info: &AssetInfoBase<String>,
+    deps: &Deps,
+) -> Result<AssetInfo, FeeConfigError> {
+    info.check(deps.api, None)
+        .map_err(|source| FeeConfigError::InvalidAsset {
+            asset: format!("{:?}", info),
+            source,
+        })
+}
+
+/// Validates the assets of a list of per-asset settings and makes sure that
+/// no asset is listed more than once. Errors are pushed to `errors` and the
+/// offending entries are left out of the result.
+fn check_asset_entries<V: Clone>(
+    entries: &[(AssetInfoBase<String>, V)],
+    deps: &Deps,
+    name: &str,
+    errors: &mut Vec<FeeConfigError>,
+) -> Vec<(AssetInfo, V)> {
+    let mut checked: Vec<(AssetInfo, V)> = vec![];
+    for (info, value) in entries {
+        match check_asset_info(info, deps) {
+            Ok(info) if checked.iter().any(|(other, _)| *other == info) => {
+                errors.push(FeeConfigError::DuplicateAsset {
+                    kind: name.to_string(),
+                    asset: info,
+                });
+            }
+            Ok(info) => checked.push((info, value.clone())),
+            Err(e) => errors.push(e),
+        }
+    }
+    checked
+}
+
+/// Converts an `AssetList` containing only native assets into `Coins`.
+fn assets_to_coins(assets: &AssetList) -> Result<Coins, FeeConfigError> {
+    Ok(assets
+        .into_iter()
+        .map(|a| {
+            a.clone()
+                .try_into()
+                .map_err(|source| FeeConfigError::AssetConversion {
+                    asset: a.clone(),
+                    source,
+                })
+        })
+        .collect::<Result<Vec<Coin>, FeeConfigError>>()?
+        .try_into()?)
+}
+
+impl From<FeeConfig<Addr>> for FeeConfig<String> {
+    fn from(value: FeeConfig<Addr>) -> Self {
+        Self {
             fee_rate: value.fee_rate,
             fee_recipients: value
                 .fee_recipients
                 .into_iter()
-                .map(|(addr, percentage)| (addr.to_string(), percentage))
+                .map(|(recipient, weight)| (recipient.into(), weight))
+                .collect(),
+            distribution: value.distribution,
+            rounding: value.rounding,
+            fee_bounds: value
+                .fee_bounds
+                .into_iter()
+                .map(|(info, bounds)| (info.into(), bounds))
+                .collect(),
+            fee_tiers: value
+                .fee_tiers
+                .into_iter()
+                .map(|(info, tiers)| (info.into(), tiers))
+                .collect(),
+            fee_rate_overrides: value
+                .fee_rate_overrides
+                .into_iter()
+                .map(|(info, rate)| (info.into(), rate))
+                .collect(),
+            fee_exemptions: value
+                .fee_exemptions
+                .into_iter()
+                .map(|addr| addr.to_string())
                 .collect(),
         }
     }
@@ -247,8 +1192,51 @@ impl From<FeeConfig<Addr>> for FeeConfig<String> {
 #[cfg(test)]
 pub mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
-    use cosmwasm_std::{coin, Addr, BankMsg, Coins, CosmosMsg, Decimal, Uint128};
-    use cw_asset::{Asset, AssetInfo, AssetList};
+    use cosmwasm_std::{
+        coin, from_json, to_json_binary, Addr, BankMsg, Coins, CosmosMsg, Decimal, StdError,
+        Uint128, WasmMsg,
+    };
+    use cw_asset::{Asset, AssetInfo, AssetInfoUnchecked, AssetList};
+
+    use super::{
+        Distribution, FeeBounds, FeeBreakdown, FeeConfigError, FeeConfigLimits, FeeRecipient,
+        FeeTier, FeeTiers, Rounding, TierMode, ValidationMode, Weight,
+    };
+
+    const THREE_RECIPIENTS: &[(&str, u64)] = &[("addr1", 50), ("addr2", 25), ("addr3", 25)];
+
+    const UUSDC_TIERS: [FeeTier; 2] = [
+        FeeTier {
+            threshold: Uint128::zero(),
+            rate: Decimal::percent(10),
+        },
+        FeeTier {
+            threshold: Uint128::new(1000),
+            rate: Decimal::percent(5),
+        },
+    ];
+
+    /// Returns a fee config with the specified fee rate that splits the fee
+    /// between addresses by percentage. Tests set any other fields with struct
+    /// update syntax.
+    pub(crate) fn test_fee_config(
+        fee_rate: Decimal,
+        recipients: &[(&str, u64)],
+    ) -> super::FeeConfig<Addr> {
+        super::FeeConfig {
+            fee_rate,
+            fee_recipients: recipients
+                .iter()
+                .map(|(addr, percent)| {
+                    (
+                        Addr::unchecked(*addr).into(),
+                        Decimal::percent(*percent).into(),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn fee_config_rate_cannot_be_larger_than_one() {
@@ -257,6 +1245,7 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::one() + Decimal::percent(1),
             fee_recipients: vec![],
+            ..Default::default()
         };
         assert!(fee_config
             .check(&deps.as_ref())
@@ -272,9 +1261,10 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                ("addr1".to_string(), Decimal::percent(20)),
-                ("addr2".to_string(), Decimal::percent(50)),
+                ("addr1".to_string().into(), Decimal::percent(20).into()),
+                ("addr2".to_string().into(), Decimal::percent(50).into()),
             ],
+            ..Default::default()
         };
         assert!(fee_config
             .check(&deps.as_ref())
@@ -290,9 +1280,10 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                ("addr1".to_string(), Decimal::percent(100)),
-                ("addr2".to_string(), Decimal::zero()),
+                ("addr1".to_string().into(), Decimal::percent(100).into()),
+                ("addr2".to_string().into(), Decimal::zero().into()),
             ],
+            ..Default::default()
         };
         assert!(fee_config
             .check(&deps.as_ref())
@@ -307,7 +1298,11 @@ pub mod tests {
 
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
-            fee_recipients: vec![(Addr::unchecked("addr1"), Decimal::percent(100))],
+            fee_recipients: vec![(
+                Addr::unchecked("addr1").into(),
+                Decimal::percent(100).into(),
+            )],
+            ..Default::default()
         };
         let asset = Asset::new(AssetInfo::native("uusdc"), 100u128);
         let (msgs, asset_after_fee) = fee_config.fee_msgs_from_asset(asset, &env).unwrap();
@@ -328,7 +1323,11 @@ pub mod tests {
 
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
-            fee_recipients: vec![(Addr::unchecked("addr1"), Decimal::percent(100))],
+            fee_recipients: vec![(
+                Addr::unchecked("addr1").into(),
+                Decimal::percent(100).into(),
+            )],
+            ..Default::default()
         };
         let coin = coin(100u128, "uusdc");
         let (msgs, coin_after_fee) = fee_config.fee_msgs_from_coin(coin.clone(), &env).unwrap();
@@ -349,7 +1348,11 @@ pub mod tests {
 
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
-            fee_recipients: vec![(Addr::unchecked("addr1"), Decimal::percent(100))],
+            fee_recipients: vec![(
+                Addr::unchecked("addr1").into(),
+                Decimal::percent(100).into(),
+            )],
+            ..Default::default()
         };
         let coins = Coins::try_from(vec![coin(100u128, "uusdc")]).unwrap();
         let (msgs, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
@@ -374,6 +1377,7 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::zero(),
             fee_recipients: vec![],
+            ..Default::default()
         };
         let asset = Asset::new(AssetInfo::native("uusdc"), 100u128);
         let (msgs, asset_after_fee) = fee_config.fee_msgs_from_asset(asset, &env).unwrap();
@@ -388,6 +1392,7 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::zero(),
             fee_recipients: vec![],
+            ..Default::default()
         };
         let coins = Coins::try_from(vec![coin(100u128, "uusdc")]).unwrap();
         let (msgs, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
@@ -404,7 +1409,11 @@ pub mod tests {
 
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
-            fee_recipients: vec![(Addr::unchecked("addr1"), Decimal::percent(100))],
+            fee_recipients: vec![(
+                Addr::unchecked("addr1").into(),
+                Decimal::percent(100).into(),
+            )],
+            ..Default::default()
         };
         let assets = vec![
             Asset::new(AssetInfo::native("uusdc"), 100u128),
@@ -412,21 +1421,13 @@ pub mod tests {
         ]
         .into();
         let (msgs, assets_after_fee) = fee_config.fee_msgs_from_assets(&assets, &env).unwrap();
-        println!("{:?}", msgs);
-        assert_eq!(msgs.len(), 2);
-        assert_eq!(
-            msgs[0],
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr1".to_string(),
-                amount: vec![coin(1u128, "uusdc".to_string())]
-            })
-        );
+        // Native fees are sent in a single message, sorted by denom
         assert_eq!(
-            msgs[1],
-            CosmosMsg::Bank(BankMsg::Send {
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
                 to_address: "addr1".to_string(),
-                amount: vec![coin(2u128, "uatom".to_string())]
-            })
+                amount: vec![coin(2u128, "uatom"), coin(1u128, "uusdc")]
+            })]
         );
         assert_eq!(assets_after_fee.to_vec()[0].amount, Uint128::new(99));
         assert_eq!(assets_after_fee.to_vec()[1].amount, Uint128::new(198));
@@ -439,9 +1440,10 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                (Addr::unchecked("addr1"), Decimal::percent(50)),
-                (Addr::unchecked("addr2"), Decimal::percent(50)),
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(50).into()),
             ],
+            ..Default::default()
         };
         let assets = vec![
             Asset::new(AssetInfo::native("uusdc"), 1000u128),
@@ -449,6 +1451,28 @@ pub mod tests {
         ]
         .into();
         let (msgs, assets_after_fee) = fee_config.fee_msgs_from_assets(&assets, &env).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr1".to_string(),
+                    amount: vec![coin(10u128, "uatom"), coin(5u128, "uusdc")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr2".to_string(),
+                    amount: vec![coin(10u128, "uatom"), coin(5u128, "uusdc")]
+                }),
+            ]
+        );
+        assert_eq!(assets_after_fee.to_vec()[0].amount, Uint128::new(990));
+        assert_eq!(assets_after_fee.to_vec()[1].amount, Uint128::new(1980));
+
+        // The per-asset layout sends one message per asset and recipient
+        let msgs = fee_config
+            .fee_breakdown(&assets, &env)
+            .unwrap()
+            .transfer_msgs_per_asset(&env)
+            .unwrap();
         assert_eq!(msgs.len(), 4);
         assert_eq!(
             msgs[0],
@@ -478,8 +1502,6 @@ pub mod tests {
                 amount: vec![coin(10u128, "uatom".to_string())]
             })
         );
-        assert_eq!(assets_after_fee.to_vec()[0].amount, Uint128::new(990));
-        assert_eq!(assets_after_fee.to_vec()[1].amount, Uint128::new(1980));
     }
 
     #[test]
@@ -489,29 +1511,22 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                (Addr::unchecked("addr1"), Decimal::percent(50)),
-                (Addr::unchecked("addr2"), Decimal::percent(50)),
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(50).into()),
             ],
+            ..Default::default()
         };
         let coins =
             Coins::try_from(vec![coin(1000u128, "uusdc"), coin(2000u128, "uatom")]).unwrap();
         let (msgs, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
-        assert_eq!(msgs.len(), 4);
-        assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Send {
-            to_address: "addr1".to_string(),
-            amount: vec![coin(5u128, "uusdc".to_string())]
-        })));
+        assert_eq!(msgs.len(), 2);
         assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Send {
             to_address: "addr1".to_string(),
-            amount: vec![coin(10u128, "uatom".to_string())]
-        })));
-        assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Send {
-            to_address: "addr2".to_string(),
-            amount: vec![coin(5u128, "uusdc".to_string())]
+            amount: vec![coin(10u128, "uatom"), coin(5u128, "uusdc")]
         })));
         assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Send {
             to_address: "addr2".to_string(),
-            amount: vec![coin(10u128, "uatom".to_string())]
+            amount: vec![coin(10u128, "uatom"), coin(5u128, "uusdc")]
         })));
         assert_eq!(
             coins_after_fee,
@@ -526,6 +1541,7 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::zero(),
             fee_recipients: vec![],
+            ..Default::default()
         };
         let assets = vec![
             Asset::new(AssetInfo::native("uusdc"), 100u128),
@@ -544,7 +1560,11 @@ pub mod tests {
 
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
-            fee_recipients: vec![(Addr::unchecked("addr1"), Decimal::percent(100))],
+            fee_recipients: vec![(
+                Addr::unchecked("addr1").into(),
+                Decimal::percent(100).into(),
+            )],
+            ..Default::default()
         };
         let assets: AssetList = vec![
             Asset::native("uusdc", 100u128),
@@ -570,9 +1590,10 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                (Addr::unchecked("addr1"), Decimal::percent(80)),
-                (Addr::unchecked("addr2"), Decimal::percent(20)),
+                (Addr::unchecked("addr1").into(), Decimal::percent(80).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(20).into()),
             ],
+            ..Default::default()
         };
         let input = coin(100u128, "uusdc");
         let msgs = fee_config.transfer_coin_msgs(&input, &env).unwrap();
@@ -600,40 +1621,25 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                (Addr::unchecked("addr1"), Decimal::percent(80)),
-                (Addr::unchecked("addr2"), Decimal::percent(20)),
+                (Addr::unchecked("addr1").into(), Decimal::percent(80).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(20).into()),
             ],
+            ..Default::default()
         };
         let input = Coins::try_from(vec![coin(100u128, "uusdc"), coin(200u128, "uatom")]).unwrap();
         let msgs = fee_config.transfer_coins_msgs(&input, &env).unwrap();
-        assert_eq!(msgs.len(), 4);
-        assert_eq!(
-            msgs[0],
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr1".to_string(),
-                amount: vec![coin(160u128, "uatom".to_string())]
-            }),
-        );
         assert_eq!(
-            msgs[1],
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr1".to_string(),
-                amount: vec![coin(80u128, "uusdc".to_string())]
-            }),
-        );
-        assert_eq!(
-            msgs[2],
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr2".to_string(),
-                amount: vec![coin(40u128, "uatom".to_string())]
-            }),
-        );
-        assert_eq!(
-            msgs[3],
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr2".to_string(),
-                amount: vec![coin(20u128, "uusdc".to_string())]
-            }),
+            msgs,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr1".to_string(),
+                    amount: vec![coin(160u128, "uatom"), coin(80u128, "uusdc")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr2".to_string(),
+                    amount: vec![coin(40u128, "uatom"), coin(20u128, "uusdc")]
+                }),
+            ]
         );
     }
 
@@ -642,9 +1648,10 @@ pub mod tests {
         let fee_config = super::FeeConfig {
             fee_rate: Decimal::percent(1),
             fee_recipients: vec![
-                (Addr::unchecked("addr1"), Decimal::percent(80)),
-                (Addr::unchecked("addr2"), Decimal::percent(20)),
+                (Addr::unchecked("addr1").into(), Decimal::percent(80).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(20).into()),
             ],
+            ..Default::default()
         };
         assert_eq!(
             fee_config.recipient_weight(&Addr::unchecked("addr1")),
@@ -659,4 +1666,1853 @@ pub mod tests {
             Decimal::zero()
         );
     }
+
+    #[test]
+    fn split_amount_independent_leaves_remainder() {
+        let fee_config = super::FeeConfig {
+            distribution: Distribution::Independent,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(3)).unwrap(),
+            vec![Uint128::new(1), Uint128::zero(), Uint128::zero()]
+        );
+    }
+
+    #[test]
+    fn split_amount_largest_remainder_distributes_everything() {
+        let fee_config = super::FeeConfig {
+            distribution: Distribution::LargestRemainder,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(3)).unwrap(),
+            vec![Uint128::new(1), Uint128::new(1), Uint128::new(1)]
+        );
+        // Ties are resolved in favour of the first recipient
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(2)).unwrap(),
+            vec![Uint128::new(1), Uint128::new(1), Uint128::zero()]
+        );
+    }
+
+    #[test]
+    fn split_amount_largest_remainder_with_weights_above_one() {
+        // Weights that sum to more than one are rejected by `check`, but must
+        // not make an unchecked config panic
+        let fee_config = super::FeeConfig {
+            distribution: Distribution::LargestRemainder,
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 60), ("addr2", 60)])
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(10)).unwrap(),
+            vec![Uint128::new(6), Uint128::new(4)]
+        );
+    }
+
+    #[test]
+    fn split_amount_last_recipient_absorbs_remainder() {
+        let fee_config = super::FeeConfig {
+            distribution: Distribution::LastRecipient,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(3)).unwrap(),
+            vec![Uint128::new(1), Uint128::zero(), Uint128::new(2)]
+        );
+    }
+
+    #[test]
+    fn transfer_coin_msgs_sends_full_amount_with_largest_remainder() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(33).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(33).into()),
+                (Addr::unchecked("addr3").into(), Decimal::percent(34).into()),
+            ],
+            distribution: Distribution::LargestRemainder,
+            ..Default::default()
+        };
+        let msgs = fee_config
+            .transfer_coin_msgs(&coin(10u128, "uusdc"), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr1".to_string(),
+                    amount: vec![coin(3u128, "uusdc")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr2".to_string(),
+                    amount: vec![coin(3u128, "uusdc")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr3".to_string(),
+                    amount: vec![coin(4u128, "uusdc")]
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn distribution_defaults_to_independent_when_deserializing() {
+        let fee_config: super::FeeConfig<String> =
+            from_json(r#"{"fee_rate":"0.01","fee_recipients":[["addr1","1"]]}"#).unwrap();
+        assert_eq!(fee_config.distribution, Distribution::Independent);
+    }
+
+    #[test]
+    fn fee_msgs_from_coin_rounds_fee_up_with_ceil_rounding() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            rounding: Rounding::Ceil,
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        let (msgs, coin_after_fee) = fee_config
+            .fee_msgs_from_coin(coin(10u128, "uusdc"), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(1u128, "uusdc")]
+            })]
+        );
+        assert_eq!(coin_after_fee, coin(9u128, "uusdc"));
+    }
+
+    #[test]
+    fn fee_amount_never_exceeds_input() {
+        let fee_config = super::FeeConfig::<Addr> {
+            fee_rate: Decimal::one(),
+            rounding: Rounding::Ceil,
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config
+                .fee_amount(&Asset::native("uusdc", 7u128))
+                .unwrap(),
+            Uint128::new(7)
+        );
+    }
+
+    #[test]
+    fn split_amount_with_ceil_rounding_never_exceeds_amount() {
+        let fee_config = super::FeeConfig {
+            rounding: Rounding::Ceil,
+            distribution: Distribution::Independent,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(3)).unwrap(),
+            vec![Uint128::new(2), Uint128::new(1), Uint128::zero()]
+        );
+    }
+
+    #[test]
+    fn split_amount_with_ceil_rounding_favours_earlier_recipients() {
+        let fee_config = super::FeeConfig {
+            rounding: Rounding::Ceil,
+            ..super::FeeConfig::new_with_shares(
+                Decimal::percent(1),
+                &[
+                    ("addr1".to_string(), 1),
+                    ("addr2".to_string(), 1),
+                    ("addr3".to_string(), 1),
+                ],
+            )
+        }
+        .check(&mock_dependencies().as_ref())
+        .unwrap();
+        // Each share of 1.33 is rounded up to 2 until the amount runs out
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(4)).unwrap(),
+            vec![Uint128::new(2), Uint128::new(2), Uint128::zero()]
+        );
+
+        let fee_config = super::FeeConfig {
+            distribution: Distribution::LargestRemainder,
+            ..fee_config
+        };
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(4)).unwrap(),
+            vec![Uint128::new(2), Uint128::new(1), Uint128::new(1)]
+        );
+    }
+
+    #[test]
+    fn split_amount_with_half_even_rounding() {
+        let fee_config = super::FeeConfig {
+            rounding: Rounding::HalfEven,
+            distribution: Distribution::LastRecipient,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        // 1.5 rounds to 2, 0.75 rounds to 1 and the last recipient gets the rest
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(3)).unwrap(),
+            vec![Uint128::new(2), Uint128::new(1), Uint128::zero()]
+        );
+        // 2.5 rounds to 2, 1.25 rounds to 1 and the last recipient gets the rest
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(5)).unwrap(),
+            vec![Uint128::new(2), Uint128::new(1), Uint128::new(2)]
+        );
+    }
+
+    #[test]
+    fn rounding_survives_serialization_round_trip() {
+        let fee_config: super::FeeConfig<String> = super::FeeConfig {
+            rounding: Rounding::HalfUp,
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        }
+        .into();
+        let json = to_json_binary(&fee_config).unwrap();
+        let deserialized: super::FeeConfig<String> = from_json(&json).unwrap();
+        assert_eq!(deserialized, fee_config);
+
+        let legacy: super::FeeConfig<String> =
+            from_json(r#"{"fee_rate":"0.01","fee_recipients":[["addr1","1"]]}"#).unwrap();
+        assert_eq!(legacy.rounding, Rounding::Floor);
+    }
+
+    #[test]
+    fn fee_breakdown_works() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(25).into(),
+                ),
+                (Addr::unchecked("addr2").into(), Decimal::percent(25).into()),
+            ],
+            ..Default::default()
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::native("uatom", 30u128),
+        ]
+        .into();
+        let breakdown = fee_config.fee_breakdown(&assets, &env).unwrap();
+        assert_eq!(
+            breakdown,
+            FeeBreakdown {
+                fees: vec![
+                    Asset::native("uusdc", 100u128),
+                    Asset::native("uatom", 3u128)
+                ]
+                .into(),
+                recipients: vec![
+                    (
+                        Addr::unchecked("addr1").into(),
+                        vec![
+                            Asset::native("uusdc", 50u128),
+                            Asset::native("uatom", 1u128)
+                        ]
+                        .into()
+                    ),
+                    (
+                        Addr::unchecked("addr2").into(),
+                        vec![Asset::native("uusdc", 25u128)].into()
+                    ),
+                ],
+                // The contract's own share plus the rounding remainder of uatom
+                retained: vec![
+                    Asset::native("uusdc", 25u128),
+                    Asset::native("uatom", 2u128)
+                ]
+                .into(),
+                assets_after_fees: vec![
+                    Asset::native("uusdc", 900u128),
+                    Asset::native("uatom", 27u128)
+                ]
+                .into(),
+            }
+        );
+        assert_eq!(
+            breakdown.recipient_amounts(&Addr::unchecked("addr2")),
+            vec![Asset::native("uusdc", 25u128)].into()
+        );
+        assert_eq!(
+            breakdown.recipient_amounts(&Addr::unchecked("addr3")),
+            AssetList::new()
+        );
+        assert_eq!(
+            breakdown.transfer_msgs(&env).unwrap(),
+            fee_config.fee_msgs_from_assets(&assets, &env).unwrap().0
+        );
+    }
+
+    #[test]
+    fn burn_recipient() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Weight::Shares(3)),
+                (FeeRecipient::Burn, Weight::Shares(1)),
+            ],
+            ..Default::default()
+        }
+        .check(&deps.as_ref())
+        .unwrap();
+        assert_eq!(
+            fee_config.fee_recipient_weight(&FeeRecipient::Burn),
+            Decimal::percent(25)
+        );
+        assert_eq!(
+            fee_config.recipient_weight(&Addr::unchecked("addr1")),
+            Decimal::percent(75)
+        );
+
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 200u128),
+        ]
+        .into();
+        let breakdown = fee_config.fee_breakdown(&assets, &env).unwrap();
+        assert_eq!(
+            breakdown.fee_recipient_amounts(&FeeRecipient::Burn),
+            vec![
+                Asset::native("uusdc", 25u128),
+                Asset::cw20(Addr::unchecked("token"), 5u128)
+            ]
+            .into()
+        );
+
+        let msgs = fee_config
+            .transfer_assets_msgs(&breakdown.fees, &env)
+            .unwrap();
+        assert!(msgs.contains(&CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(25, "uusdc")],
+        })));
+        assert!(msgs.contains(&CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(5),
+            })
+            .unwrap(),
+            funds: vec![],
+        })));
+
+        // Burning is a single recipient
+        let err = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (FeeRecipient::Burn, Weight::Shares(1)),
+                (FeeRecipient::Burn, Weight::Shares(1)),
+            ],
+            ..Default::default()
+        }
+        .check(&deps.as_ref())
+        .unwrap_err();
+        assert_eq!(
+            err,
+            FeeConfigError::DuplicateRecipient {
+                recipient: "burn".to_string()
+            }
+        );
+    }
+
+    #[cfg(feature = "ibc")]
+    #[test]
+    fn ibc_recipient_shares_of_cw20_fees_are_retained() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let ibc = FeeRecipient::Ibc {
+            channel_id: "channel-0".to_string(),
+            address: "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t".to_string(),
+            timeout_seconds: 600,
+        };
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::percent(50).into()),
+                (ibc.clone(), Decimal::percent(50).into()),
+            ],
+            ..Default::default()
+        };
+        let checked = fee_config.check(&deps.as_ref()).unwrap();
+
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 1000u128),
+        ]
+        .into();
+        let breakdown = checked.fee_breakdown(&assets, &env).unwrap();
+        assert_eq!(
+            breakdown.fee_recipient_amounts(&ibc.check(&deps.as_ref()).unwrap()),
+            vec![Asset::native("uusdc", 50u128)].into()
+        );
+        assert_eq!(
+            breakdown.retained,
+            vec![Asset::cw20(Addr::unchecked("token"), 50u128)].into()
+        );
+        assert_eq!(
+            checked.fee_msgs_from_assets(&assets, &env).unwrap().0.len(),
+            3
+        );
+
+        // Exempting a cw20 asset with a zero rate override is fine
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfoUnchecked::cw20("token"), Decimal::zero())],
+            ..fee_config
+        };
+        let checked = fee_config.check(&deps.as_ref()).unwrap();
+        let breakdown = checked.fee_breakdown(&assets, &env).unwrap();
+        assert_eq!(breakdown.fees, vec![Asset::native("uusdc", 100u128)].into());
+        assert_eq!(breakdown.retained, AssetList::new());
+
+        // Explicitly charging fees for a cw20 asset is rejected
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfoUnchecked::cw20("token"), Decimal::percent(5))],
+            ..fee_config
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::UnsupportedAsset {
+                recipient: ibc.to_string(),
+                asset: AssetInfo::cw20(Addr::unchecked("token")),
+            }
+        );
+    }
+
+    #[cfg(feature = "community-pool")]
+    #[test]
+    fn community_pool_without_fallback_retains_cw20_shares() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![(
+                FeeRecipient::CommunityPool {
+                    cw20_fallback: None,
+                },
+                Decimal::one().into(),
+            )],
+            ..Default::default()
+        };
+        let checked = fee_config.check(&deps.as_ref()).unwrap();
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 1000u128),
+        ]
+        .into();
+        let breakdown = checked.fee_breakdown(&assets, &env).unwrap();
+        assert_eq!(
+            breakdown.retained,
+            vec![Asset::cw20(Addr::unchecked("token"), 100u128)].into()
+        );
+        assert_eq!(breakdown.transfer_msgs(&env).unwrap().len(), 1);
+
+        // Exempting a cw20 asset with a zero rate override doesn't require a
+        // fallback, and neither does capping its fee
+        let exempt = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfoUnchecked::cw20("token"),
+                FeeBounds {
+                    min: None,
+                    max: Some(Uint128::new(10)),
+                },
+            )],
+            fee_rate_overrides: vec![(AssetInfoUnchecked::cw20("token"), Decimal::zero())],
+            ..fee_config.clone()
+        };
+        let breakdown = exempt
+            .check(&deps.as_ref())
+            .unwrap()
+            .fee_breakdown(&assets, &env)
+            .unwrap();
+        assert_eq!(breakdown.fees, vec![Asset::native("uusdc", 100u128)].into());
+        assert_eq!(breakdown.retained, AssetList::new());
+
+        // Charging a minimum fee for a cw20 asset requires a fallback
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfoUnchecked::cw20("token"),
+                FeeBounds {
+                    min: Some(Uint128::new(10)),
+                    max: None,
+                },
+            )],
+            ..fee_config
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::UnsupportedAsset {
+                recipient: "community_pool".to_string(),
+                asset: AssetInfo::cw20(Addr::unchecked("token")),
+            }
+        );
+    }
+
+    #[test]
+    fn transfer_breakdown_with_zero_fee_rate_retains_everything() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig::<Addr>::default();
+        let assets: AssetList = vec![Asset::native("uusdc", 100u128)].into();
+        let breakdown = fee_config.transfer_breakdown(&assets, &env).unwrap();
+        assert_eq!(breakdown.fees, assets);
+        assert_eq!(breakdown.retained, assets);
+        assert!(breakdown.recipients.is_empty());
+        assert!(breakdown.transfer_msgs(&env).unwrap().is_empty());
+    }
+
+    #[test]
+    fn gross_up_amount_nets_exactly_the_target() {
+        let env = mock_env();
+
+        for rounding in [
+            Rounding::Floor,
+            Rounding::Ceil,
+            Rounding::HalfUp,
+            Rounding::HalfEven,
+        ] {
+            for fee_rate in [
+                Decimal::zero(),
+                Decimal::percent(1),
+                Decimal::permille(3),
+                Decimal::percent(33),
+                Decimal::percent(99),
+            ] {
+                let fee_config = super::FeeConfig {
+                    fee_rate,
+                    fee_recipients: vec![(Addr::unchecked("addr1").into(), Decimal::one().into())],
+                    rounding,
+                    ..Default::default()
+                };
+                for net in (0u128..300).chain([12_345_678, 1_000_000_000_000]) {
+                    let gross = fee_config
+                        .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::new(net))
+                        .unwrap();
+                    let (_, coin_after_fee) = fee_config
+                        .fee_msgs_from_coin(coin(gross.u128(), "uusdc"), &env)
+                        .unwrap();
+                    assert_eq!(coin_after_fee.amount.u128(), net);
+                    if !gross.is_zero() {
+                        // The gross amount is the smallest that nets the target
+                        let (_, coin_after_fee) = fee_config
+                            .fee_msgs_from_coin(coin(gross.u128() - 1, "uusdc"), &env)
+                            .unwrap();
+                        assert!(coin_after_fee.amount.u128() < net);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gross_up_fails_with_full_fee_rate() {
+        let fee_config = super::FeeConfig::<Addr> {
+            fee_rate: Decimal::one(),
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config
+                .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::zero())
+                .unwrap(),
+            Uint128::zero()
+        );
+        assert!(fee_config
+            .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::one())
+            .unwrap_err()
+            .to_string()
+            .contains("Can't gross up an amount when the fee rate is 100%"));
+    }
+
+    #[test]
+    fn gross_up_fails_on_overflow() {
+        let fee_config = super::FeeConfig::<Addr> {
+            fee_rate: Decimal::percent(1),
+            ..Default::default()
+        };
+        assert!(fee_config
+            .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::MAX)
+            .is_err());
+    }
+
+    #[test]
+    fn gross_up_assets_and_coins_work() {
+        let fee_config = super::FeeConfig::<Addr> {
+            fee_rate: Decimal::percent(1),
+            rounding: Rounding::Ceil,
+            ..Default::default()
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 99u128),
+            Asset::native("uatom", 198u128),
+        ]
+        .into();
+        assert_eq!(
+            fee_config.gross_up_assets(&assets).unwrap(),
+            vec![
+                Asset::native("uusdc", 100u128),
+                Asset::native("uatom", 200u128)
+            ]
+            .into()
+        );
+        assert_eq!(
+            fee_config
+                .gross_up_asset(&Asset::native("uusdc", 99u128))
+                .unwrap(),
+            Asset::native("uusdc", 100u128)
+        );
+
+        let coins = Coins::try_from(vec![coin(99u128, "uusdc"), coin(198u128, "uatom")]).unwrap();
+        assert_eq!(
+            fee_config.gross_up_coins(&coins).unwrap(),
+            Coins::try_from(vec![coin(100u128, "uusdc"), coin(200u128, "uatom")]).unwrap()
+        );
+        assert_eq!(
+            fee_config.gross_up_coin(&coin(99u128, "uusdc")).unwrap(),
+            coin(100u128, "uusdc")
+        );
+    }
+
+    #[test]
+    fn fee_msgs_from_assets_with_retained_reports_contract_share() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(70).into()),
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(30).into(),
+                ),
+            ],
+            ..Default::default()
+        };
+        let assets: AssetList = vec![Asset::native("uusdc", 1005u128)].into();
+        let (msgs, assets_after_fee, retained) = fee_config
+            .fee_msgs_from_assets_with_retained(&assets, &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(70u128, "uusdc")]
+            })]
+        );
+        assert_eq!(
+            assets_after_fee,
+            vec![Asset::native("uusdc", 905u128)].into()
+        );
+        // 30% of the 100 uusdc fee is the contract's own share
+        assert_eq!(retained, vec![Asset::native("uusdc", 30u128)].into());
+    }
+
+    #[test]
+    fn fee_msgs_from_coins_with_retained_works() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(50).into(),
+                ),
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+            ],
+            ..Default::default()
+        };
+        let coins = Coins::try_from(vec![coin(1000u128, "uusdc"), coin(30u128, "uatom")]).unwrap();
+        let (msgs, coins_after_fee, retained) = fee_config
+            .fee_msgs_from_coins_with_retained(&coins, &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(1u128, "uatom"), coin(50u128, "uusdc")]
+            })]
+        );
+        assert_eq!(
+            coins_after_fee,
+            Coins::try_from(vec![coin(900u128, "uusdc"), coin(27u128, "uatom")]).unwrap()
+        );
+        // Includes the uatom rounding remainder
+        assert_eq!(
+            retained,
+            Coins::try_from(vec![coin(50u128, "uusdc"), coin(2u128, "uatom")]).unwrap()
+        );
+    }
+
+    #[test]
+    fn transfer_assets_msgs_with_retained_works() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (
+                    env.contract.address.clone().into(),
+                    Decimal::percent(50).into(),
+                ),
+            ],
+            ..Default::default()
+        };
+        let assets: AssetList = vec![Asset::native("uusdc", 11u128)].into();
+        let (msgs, retained) = fee_config
+            .transfer_assets_msgs_with_retained(&assets, &env)
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(retained, vec![Asset::native("uusdc", 6u128)].into());
+    }
+
+    #[test]
+    fn fee_config_min_fee_cannot_be_larger_than_max_fee() {
+        let deps = mock_dependencies();
+
+        let fee_config: super::FeeConfig<String> = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(10)),
+                    max: Some(Uint128::new(5)),
+                },
+            )],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        }
+        .into();
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Minimum fee 10 can't be larger than maximum fee 5"));
+    }
+
+    #[test]
+    fn fee_config_rejects_duplicate_and_invalid_fee_bounds() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![("addr1".to_string().into(), Decimal::one().into())],
+            fee_bounds: vec![
+                (AssetInfoUnchecked::native("uusdc"), FeeBounds::default()),
+                (AssetInfoUnchecked::native("uusdc"), FeeBounds::default()),
+            ],
+            ..Default::default()
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate fee bounds for asset native:uusdc"));
+
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(AssetInfoUnchecked::cw20("a"), FeeBounds::default())],
+            ..fee_config
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid asset"));
+    }
+
+    #[test]
+    fn fee_config_with_min_fee_requires_recipients() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::zero(),
+            fee_bounds: vec![(
+                AssetInfoUnchecked::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::one()),
+                    max: None,
+                },
+            )],
+            ..Default::default()
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Sum of fee recipient percentages must be 100%"));
+    }
+
+    #[test]
+    fn fee_msgs_from_coin_applies_fee_bounds() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(5)),
+                    max: Some(Uint128::new(20)),
+                },
+            )],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        // 1% of 100 is below the minimum fee
+        let (msgs, coin_after_fee) = fee_config
+            .fee_msgs_from_coin(coin(100u128, "uusdc"), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(5u128, "uusdc")]
+            })]
+        );
+        assert_eq!(coin_after_fee, coin(95u128, "uusdc"));
+
+        // 1% of 10000 is above the maximum fee
+        let (_, coin_after_fee) = fee_config
+            .fee_msgs_from_coin(coin(10000u128, "uusdc"), &env)
+            .unwrap();
+        assert_eq!(coin_after_fee, coin(9980u128, "uusdc"));
+
+        // Other assets are not bounded
+        let (_, coin_after_fee) = fee_config
+            .fee_msgs_from_coin(coin(100u128, "uatom"), &env)
+            .unwrap();
+        assert_eq!(coin_after_fee, coin(99u128, "uatom"));
+    }
+
+    #[test]
+    fn fee_msgs_from_coin_fails_when_amount_is_below_min_fee() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(5)),
+                    max: None,
+                },
+            )],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        assert!(fee_config
+            .fee_msgs_from_coin(coin(4u128, "uusdc"), &env)
+            .unwrap_err()
+            .to_string()
+            .contains("Amount 4 is smaller than the minimum fee 5"));
+
+        // Zero amounts are not charged
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 0u128),
+            Asset::native("uatom", 100u128),
+        ]
+        .into();
+        let (msgs, _) = fee_config.fee_msgs_from_assets(&assets, &env).unwrap();
+        assert_eq!(msgs.len(), 1);
+    }
+
+    #[test]
+    fn fee_msgs_from_asset_takes_whole_amount_at_min_fee() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(10)),
+                    max: None,
+                },
+            )],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        let (msgs, asset_after_fee) = fee_config
+            .fee_msgs_from_asset(Asset::native("uusdc", 10u128), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(10u128, "uusdc")]
+            })]
+        );
+        assert_eq!(asset_after_fee, Asset::native("uusdc", 0u128));
+    }
+
+    #[test]
+    fn gross_up_amount_respects_fee_bounds() {
+        let env = mock_env();
+
+        for (min, max) in [
+            (Some(5), None),
+            (None, Some(3)),
+            (Some(2), Some(4)),
+            (Some(7), Some(7)),
+        ] {
+            let fee_config = super::FeeConfig {
+                fee_bounds: vec![(
+                    AssetInfo::native("uusdc"),
+                    FeeBounds {
+                        min: min.map(Uint128::new),
+                        max: max.map(Uint128::new),
+                    },
+                )],
+                ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+            };
+            for net in 1u128..1000 {
+                let gross = fee_config
+                    .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::new(net))
+                    .unwrap();
+                let (_, coin_after_fee) = fee_config
+                    .fee_msgs_from_coin(coin(gross.u128(), "uusdc"), &env)
+                    .unwrap();
+                assert_eq!(coin_after_fee.amount.u128(), net);
+            }
+        }
+
+        // A maximum fee makes grossing up possible even with a 100% fee rate
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: None,
+                    max: Some(Uint128::new(10)),
+                },
+            )],
+            ..test_fee_config(Decimal::one(), &[("addr1", 100)])
+        };
+        assert_eq!(
+            fee_config
+                .gross_up_amount(&AssetInfo::native("uusdc"), Uint128::new(90))
+                .unwrap(),
+            Uint128::new(100)
+        );
+    }
+
+    #[test]
+    fn fee_config_validates_fee_tiers() {
+        let deps = mock_dependencies();
+
+        let tiered: super::FeeConfig<String> = super::FeeConfig {
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Marginal,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        }
+        .into();
+        let mut fee_config = tiered.clone();
+        fee_config.check(&deps.as_ref()).unwrap();
+
+        fee_config.fee_tiers[0].1.tiers[1].threshold = Uint128::zero();
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Fee tier thresholds must be strictly increasing"));
+
+        let mut fee_config = tiered.clone();
+        fee_config.fee_tiers.push(fee_config.fee_tiers[0].clone());
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate fee tiers for asset native:uusdc"));
+
+        // Tiers with non-zero rates require valid fee recipients, even if the
+        // fee rate is zero
+        let mut fee_config = tiered;
+        fee_config.fee_recipients = vec![];
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Sum of fee recipient percentages must be 100%"));
+    }
+
+    #[test]
+    fn fee_msgs_from_coins_uses_fee_tiers() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Marginal,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        let coins =
+            Coins::try_from(vec![coin(2000u128, "uusdc"), coin(2000u128, "uatom")]).unwrap();
+        let (_, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
+        // uusdc pays 10% of the first 1000 and 5% of the rest, uatom pays the
+        // fee rate
+        assert_eq!(
+            coins_after_fee,
+            Coins::try_from(vec![coin(1850u128, "uusdc"), coin(1980u128, "uatom")]).unwrap()
+        );
+
+        let fee_config = super::FeeConfig {
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Flat,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        };
+        let (msgs, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(100u128, "uusdc")]
+            })]
+        );
+        assert_eq!(
+            coins_after_fee,
+            Coins::try_from(vec![coin(1900u128, "uusdc"), coin(2000u128, "uatom")]).unwrap()
+        );
+    }
+
+    #[test]
+    fn gross_up_amount_works_with_fee_tiers() {
+        let env = mock_env();
+
+        let net_of = |fee_config: &super::FeeConfig<Addr>, gross: Uint128| {
+            fee_config
+                .fee_msgs_from_coin(coin(gross.u128(), "uusdc"), &env)
+                .unwrap()
+                .1
+                .amount
+        };
+        let info = AssetInfo::native("uusdc");
+
+        let fee_config = super::FeeConfig {
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Marginal,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        };
+        for net in (1u128..2000).step_by(7) {
+            let gross = fee_config
+                .gross_up_amount(&info, Uint128::new(net))
+                .unwrap();
+            assert_eq!(net_of(&fee_config, gross), Uint128::new(net));
+        }
+
+        // With flat tiers, nets between 900 and 950 can't be reached: 999 nets
+        // 900 while 1000 nets 950
+        let fee_config = super::FeeConfig {
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Flat,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        };
+        for net in (1u128..2000).step_by(7) {
+            let gross = fee_config
+                .gross_up_amount(&info, Uint128::new(net))
+                .unwrap();
+            if (901..950).contains(&net) {
+                assert_eq!(gross, Uint128::new(1000));
+            } else {
+                assert_eq!(net_of(&fee_config, gross), Uint128::new(net));
+            }
+        }
+    }
+
+    #[test]
+    fn fee_config_validates_fee_rate_overrides() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![("addr1".to_string().into(), Decimal::one().into())],
+            fee_rate_overrides: vec![(AssetInfoUnchecked::native("uusdc"), Decimal::percent(101))],
+            ..Default::default()
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Fee rate can't be higher than 100%"));
+
+        // cw20 addresses are validated
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfoUnchecked::cw20("a"), Decimal::zero())],
+            ..fee_config
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid asset"));
+
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfoUnchecked::cw20("token"), Decimal::zero())],
+            ..fee_config
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap().fee_rate_overrides,
+            vec![(AssetInfo::cw20(Addr::unchecked("token")), Decimal::zero())]
+        );
+    }
+
+    #[test]
+    fn fee_config_rejects_fee_rate_override_for_tiered_asset() {
+        let deps = mock_dependencies();
+
+        let fee_config: super::FeeConfig<String> = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfo::native("uusdc"), Decimal::percent(1))],
+            fee_tiers: vec![(
+                AssetInfo::native("uusdc"),
+                FeeTiers {
+                    mode: TierMode::Marginal,
+                    tiers: UUSDC_TIERS.to_vec(),
+                },
+            )],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        }
+        .into();
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("Asset native:uusdc can't have both fee tiers and a fee rate override"));
+    }
+
+    #[test]
+    fn fee_config_rejects_min_fee_for_exempt_asset() {
+        let deps = mock_dependencies();
+
+        let fee_config: super::FeeConfig<String> = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfo::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(5)),
+                    max: None,
+                },
+            )],
+            fee_rate_overrides: vec![(AssetInfo::native("uusdc"), Decimal::zero())],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        }
+        .into();
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::MinFeeForExemptAsset {
+                asset: AssetInfo::native("uusdc")
+            }
+        );
+
+        // A maximum fee or a non-zero override is fine
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfoUnchecked::native("uusdc"),
+                FeeBounds {
+                    min: None,
+                    max: Some(Uint128::new(5)),
+                },
+            )],
+            ..fee_config
+        };
+        fee_config.check(&deps.as_ref()).unwrap();
+        let fee_config = super::FeeConfig {
+            fee_bounds: vec![(
+                AssetInfoUnchecked::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(5)),
+                    max: None,
+                },
+            )],
+            fee_rate_overrides: vec![(AssetInfoUnchecked::native("uusdc"), Decimal::percent(2))],
+            ..fee_config
+        };
+        fee_config.check(&deps.as_ref()).unwrap();
+    }
+
+    #[test]
+    fn fee_msgs_from_assets_uses_fee_rate_overrides() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![
+                (AssetInfo::native("uatom"), Decimal::percent(5)),
+                (AssetInfo::cw20(Addr::unchecked("token")), Decimal::zero()),
+            ],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 1000u128),
+            Asset::native("uatom", 1000u128),
+            Asset::cw20(Addr::unchecked("token"), 1000u128),
+        ]
+        .into();
+        let (msgs, assets_after_fee) = fee_config.fee_msgs_from_assets(&assets, &env).unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(
+            assets_after_fee,
+            vec![
+                Asset::native("uusdc", 990u128),
+                Asset::native("uatom", 950u128),
+                Asset::cw20(Addr::unchecked("token"), 1000u128),
+            ]
+            .into()
+        );
+
+        let coins = Coins::try_from(vec![coin(1000u128, "uatom")]).unwrap();
+        let (_, coins_after_fee) = fee_config.fee_msgs_from_coins(&coins, &env).unwrap();
+        assert_eq!(
+            coins_after_fee,
+            Coins::try_from(vec![coin(950u128, "uatom")]).unwrap()
+        );
+        assert_eq!(
+            fee_config.gross_up_coin(&coin(950u128, "uatom")).unwrap(),
+            coin(999u128, "uatom")
+        );
+    }
+
+    #[test]
+    fn fee_rate_override_can_enable_fees_with_zero_fee_rate() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfo::native("uatom"), Decimal::percent(5))],
+            ..test_fee_config(Decimal::zero(), &[("addr1", 100)])
+        };
+        assert!(fee_config.charges_fees());
+        let (msgs, _) = fee_config
+            .fee_msgs_from_coin(coin(1000u128, "uatom"), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr1".to_string(),
+                amount: vec![coin(50u128, "uatom")]
+            })]
+        );
+    }
+
+    #[test]
+    fn fee_config_validates_fee_exemptions() {
+        let deps = mock_dependencies();
+
+        let fee_config: super::FeeConfig<String> = super::FeeConfig {
+            fee_exemptions: vec![Addr::unchecked("Keeper")],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        }
+        .into();
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap_err()
+            .to_string()
+            .contains("address not normalized"));
+
+        let fee_config = super::FeeConfig {
+            fee_exemptions: vec!["keeper".to_string()],
+            ..fee_config
+        };
+        assert!(fee_config
+            .check(&deps.as_ref())
+            .unwrap()
+            .is_exempt(&Addr::unchecked("keeper")));
+    }
+
+    #[test]
+    fn fee_msgs_for_payer_skips_fees_for_exempt_payers() {
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_exemptions: vec![Addr::unchecked("keeper")],
+            ..test_fee_config(Decimal::percent(1), &[("addr1", 100)])
+        };
+        let assets: AssetList = vec![Asset::native("uusdc", 100u128)].into();
+
+        let (msgs, assets_after_fee) = fee_config
+            .fee_msgs_from_assets_for_payer(&Addr::unchecked("keeper"), &assets, &env)
+            .unwrap();
+        assert!(msgs.is_empty());
+        assert_eq!(assets_after_fee, assets);
+
+        let (msgs, assets_after_fee) = fee_config
+            .fee_msgs_from_assets_for_payer(&Addr::unchecked("user"), &assets, &env)
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(
+            assets_after_fee,
+            vec![Asset::native("uusdc", 99u128)].into()
+        );
+
+        let breakdown = fee_config
+            .fee_breakdown_for_payer(&Addr::unchecked("keeper"), &assets, &env)
+            .unwrap();
+        assert!(breakdown.fees.is_empty());
+        assert_eq!(breakdown.assets_after_fees, assets);
+
+        let coins = Coins::try_from(vec![coin(100u128, "uusdc")]).unwrap();
+        let (msgs, coins_after_fee) = fee_config
+            .fee_msgs_from_coins_for_payer(&Addr::unchecked("keeper"), &coins, &env)
+            .unwrap();
+        assert!(msgs.is_empty());
+        assert_eq!(coins_after_fee, coins);
+        let (msgs, coins_after_fee) = fee_config
+            .fee_msgs_from_coins_for_payer(&Addr::unchecked("user"), &coins, &env)
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(
+            coins_after_fee,
+            Coins::try_from(vec![coin(99u128, "uusdc")]).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_errors_are_typed() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![("addr1".to_string().into(), Decimal::one().into())],
+            fee_rate_overrides: vec![(AssetInfoUnchecked::native("uusdc"), Decimal::percent(150))],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::RateTooHigh {
+                rate: Decimal::percent(150)
+            }
+        );
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::percent(20).into()),
+                ("addr2".to_string().into(), Decimal::percent(50).into()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::percent(70)
+            }
+        );
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::one().into()),
+                ("addr2".to_string().into(), Decimal::zero().into()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::ZeroWeight {
+                recipient: "addr2".to_string()
+            }
+        );
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![("ADDR1".to_string().into(), Decimal::one().into())],
+            ..Default::default()
+        };
+        assert!(matches!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::InvalidAddress { address, .. } if address == "ADDR1"
+        ));
+    }
+
+    #[test]
+    fn fee_config_error_converts_into_std_error() {
+        let err: StdError = FeeConfigError::GrossUpWithFullFeeRate.into();
+        assert_eq!(
+            err,
+            StdError::generic_err("Can't gross up an amount when the fee rate is 100%")
+        );
+
+        let err: StdError = FeeConfigError::Std(StdError::not_found("fee config")).into();
+        assert_eq!(err, StdError::not_found("fee config"));
+    }
+
+    #[test]
+    fn check_rejects_duplicate_recipients() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::percent(50)),
+                ("addr1".to_string(), Decimal::percent(50)),
+            ],
+        );
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::DuplicateRecipient {
+                recipient: "addr1".to_string()
+            }
+        );
+
+        // Addresses that only differ in case are duplicates too
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::percent(50)),
+                ("ADDR1".to_string(), Decimal::percent(50)),
+            ],
+        );
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::DuplicateRecipient {
+                recipient: "ADDR1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn check_merging_duplicates_merges_recipients() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::percent(30)),
+                ("addr2".to_string(), Decimal::percent(50)),
+                ("ADDR1".to_string(), Decimal::percent(20)),
+            ],
+        );
+        let checked = fee_config.check_merging_duplicates(&deps.as_ref()).unwrap();
+        assert_eq!(
+            checked.fee_recipients,
+            vec![
+                (Addr::unchecked("addr1").into(), Decimal::percent(50).into()),
+                (Addr::unchecked("addr2").into(), Decimal::percent(50).into()),
+            ]
+        );
+        assert_eq!(
+            checked.recipient_weight(&Addr::unchecked("addr1")),
+            Decimal::percent(50)
+        );
+
+        // The merged weights must still sum to 100%
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::percent(30)),
+                ("addr1".to_string(), Decimal::percent(30)),
+            ],
+        );
+        assert_eq!(
+            fee_config
+                .check_merging_duplicates(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::percent(60)
+            }
+        );
+
+        // The same address as different kinds of recipients is not merged
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::percent(50).into()),
+                (
+                    FeeRecipient::Contract {
+                        address: "addr1".to_string(),
+                        msg: to_json_binary(&"hook").unwrap(),
+                    },
+                    Decimal::percent(50).into(),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config
+                .check_merging_duplicates(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::DuplicateRecipient {
+                recipient: "addr1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn check_strict_validates_weights_without_fees() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::zero(),
+            &[
+                ("addr1".to_string(), Decimal::percent(20)),
+                ("addr2".to_string(), Decimal::percent(50)),
+            ],
+        );
+        fee_config.check(&deps.as_ref()).unwrap();
+        assert_eq!(
+            fee_config.check_strict(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::percent(70)
+            }
+        );
+
+        // A config without recipients and fees is still valid
+        let fee_config = super::FeeConfig::new(Decimal::zero(), &[]);
+        fee_config.check_strict(&deps.as_ref()).unwrap();
+    }
+
+    #[test]
+    fn validate_reports_all_errors() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(150),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::percent(20).into()),
+                ("addr2".to_string().into(), Decimal::zero().into()),
+                ("ADDR3".to_string().into(), Decimal::percent(50).into()),
+            ],
+            fee_bounds: vec![(
+                AssetInfoUnchecked::native("uusdc"),
+                FeeBounds {
+                    min: Some(Uint128::new(10)),
+                    max: Some(Uint128::new(5)),
+                },
+            )],
+            ..Default::default()
+        };
+        let errors = fee_config.validate(&deps.as_ref(), ValidationMode::Standard);
+        assert_eq!(errors.len(), 5);
+        assert_eq!(
+            errors[..4],
+            [
+                FeeConfigError::RateTooHigh {
+                    rate: Decimal::percent(150)
+                },
+                FeeConfigError::WeightsDoNotSumToOne {
+                    sum: Decimal::percent(70)
+                },
+                FeeConfigError::ZeroWeight {
+                    recipient: "addr2".to_string()
+                },
+                FeeConfigError::MinFeeAboveMaxFee {
+                    min: Uint128::new(10),
+                    max: Uint128::new(5)
+                },
+            ]
+        );
+        assert!(matches!(
+            &errors[4],
+            FeeConfigError::InvalidAddress { address, .. } if address == "ADDR3"
+        ));
+        assert_eq!(fee_config.check(&deps.as_ref()).unwrap_err(), errors[0]);
+
+        // A valid config has no errors in either mode
+        let fee_config: super::FeeConfig<String> =
+            test_fee_config(Decimal::percent(1), THREE_RECIPIENTS).into();
+        assert_eq!(
+            fee_config.validate(&deps.as_ref(), ValidationMode::Standard),
+            vec![]
+        );
+        assert_eq!(
+            fee_config.validate(&deps.as_ref(), ValidationMode::Strict),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn check_with_limits_enforces_limits() {
+        let deps = mock_dependencies();
+
+        let limits = FeeConfigLimits {
+            max_fee_rate: Some(Decimal::percent(5)),
+            max_recipients: Some(2),
+            weight_granularity: Some(Decimal::percent(1)),
+        };
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(5),
+            &[
+                ("addr1".to_string(), Decimal::percent(40)),
+                ("addr2".to_string(), Decimal::percent(60)),
+            ],
+        );
+        fee_config
+            .check_with_limits(&deps.as_ref(), &limits)
+            .unwrap();
+
+        let fee_config = super::FeeConfig {
+            fee_rate_overrides: vec![(AssetInfoUnchecked::native("uusdc"), Decimal::percent(6))],
+            ..fee_config
+        };
+        assert_eq!(
+            fee_config
+                .check_with_limits(&deps.as_ref(), &limits)
+                .unwrap_err(),
+            FeeConfigError::FeeRateAboveLimit {
+                rate: Decimal::percent(6),
+                max: Decimal::percent(5)
+            }
+        );
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::percent(40)),
+                ("addr2".to_string(), Decimal::percent(30)),
+                ("addr3".to_string(), Decimal::percent(30)),
+            ],
+        );
+        assert_eq!(
+            fee_config
+                .check_with_limits(&deps.as_ref(), &limits)
+                .unwrap_err(),
+            FeeConfigError::TooManyRecipients { count: 3, max: 2 }
+        );
+
+        let fee_config = super::FeeConfig::new(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), Decimal::permille(405)),
+                ("addr2".to_string(), Decimal::permille(595)),
+            ],
+        );
+        assert_eq!(
+            fee_config
+                .check_with_limits(&deps.as_ref(), &limits)
+                .unwrap_err(),
+            FeeConfigError::WeightGranularity {
+                recipient: "addr1".to_string(),
+                weight: Decimal::permille(405),
+                granularity: Decimal::percent(1)
+            }
+        );
+
+        // Unset limits are not enforced
+        fee_config
+            .check_with_limits(&deps.as_ref(), &FeeConfigLimits::default())
+            .unwrap();
+
+        // A granularity of zero is not a valid limit
+        assert_eq!(
+            fee_config
+                .check_with_limits(
+                    &deps.as_ref(),
+                    &FeeConfigLimits {
+                        weight_granularity: Some(Decimal::zero()),
+                        ..Default::default()
+                    }
+                )
+                .unwrap_err(),
+            FeeConfigError::ZeroWeightGranularity
+        );
+    }
+
+    #[test]
+    fn recipient_weight_with_zero_shares_is_zero() {
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![(Addr::unchecked("addr1").into(), Weight::Shares(0))],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config.recipient_weight(&Addr::unchecked("addr1")),
+            Decimal::zero()
+        );
+    }
+
+    #[test]
+    fn share_weights_split_exactly() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig::new_with_shares(
+            Decimal::percent(1),
+            &[
+                ("addr1".to_string(), 1),
+                ("addr2".to_string(), 1),
+                ("addr3".to_string(), 1),
+            ],
+        )
+        .check(&deps.as_ref())
+        .unwrap();
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(300)).unwrap(),
+            vec![Uint128::new(100); 3]
+        );
+        assert_eq!(
+            fee_config.recipient_weight(&Addr::unchecked("addr2")),
+            Decimal::from_ratio(1u128, 3u128)
+        );
+
+        let fee_config = super::FeeConfig::new_with_shares(
+            Decimal::percent(1),
+            &[("addr1".to_string(), 7), ("addr2".to_string(), 3)],
+        )
+        .check(&deps.as_ref())
+        .unwrap();
+        assert_eq!(
+            fee_config.split_amount(Uint128::new(1000)).unwrap(),
+            vec![Uint128::new(700), Uint128::new(300)]
+        );
+        assert_eq!(
+            fee_config.recipient_weight(&Addr::unchecked("addr1")),
+            Decimal::percent(70)
+        );
+    }
+
+    #[test]
+    fn share_weights_serialization() {
+        let json = r#"{"fee_rate":"0.01","fee_recipients":[["addr1",7],["addr2",3]]}"#;
+        let fee_config: super::FeeConfig<String> = from_json(json.as_bytes()).unwrap();
+        assert_eq!(
+            fee_config.fee_recipients,
+            vec![
+                ("addr1".to_string().into(), Weight::Shares(7)),
+                ("addr2".to_string().into(), Weight::Shares(3)),
+            ]
+        );
+
+        let json = r#"{"fee_rate":"0.01","fee_recipients":[["addr1","0.7"],["addr2","0.3"]]}"#;
+        let fee_config: super::FeeConfig<String> = from_json(json.as_bytes()).unwrap();
+        assert_eq!(
+            fee_config.fee_recipients,
+            vec![
+                (
+                    "addr1".to_string().into(),
+                    Weight::Percent(Decimal::percent(70))
+                ),
+                (
+                    "addr2".to_string().into(),
+                    Weight::Percent(Decimal::percent(30))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_rejects_mixed_weights() {
+        let deps = mock_dependencies();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(1),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Weight::Shares(1)),
+                (
+                    "addr2".to_string().into(),
+                    Weight::Percent(Decimal::percent(50)),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::MixedWeights
+        );
+
+        let fee_config = super::FeeConfig::new_with_shares(
+            Decimal::percent(1),
+            &[("addr1".to_string(), 1), ("addr2".to_string(), 0)],
+        );
+        assert_eq!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::ZeroWeight {
+                recipient: "addr2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn checked_fee_config_roundtrips_through_json() {
+        let fee_config = super::FeeConfig {
+            fee_exemptions: vec![Addr::unchecked("payer")],
+            distribution: Distribution::LargestRemainder,
+            ..test_fee_config(Decimal::percent(1), THREE_RECIPIENTS)
+        };
+        let json = to_json_binary(&fee_config).unwrap();
+        assert_eq!(
+            from_json::<super::FeeConfig<Addr>>(&json).unwrap(),
+            fee_config
+        );
+    }
+
+    #[test]
+    fn contract_recipients() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![
+                ("addr1".to_string().into(), Decimal::percent(50).into()),
+                (
+                    FeeRecipient::Contract {
+                        address: "staking".to_string(),
+                        msg: to_json_binary(&"distribute").unwrap(),
+                    },
+                    Decimal::percent(50).into(),
+                ),
+            ],
+            ..Default::default()
+        };
+        let checked = fee_config.check(&deps.as_ref()).unwrap();
+        assert_eq!(
+            checked.recipient_weight(&Addr::unchecked("staking")),
+            Decimal::percent(50)
+        );
+
+        let msgs = checked
+            .transfer_assets_msgs(&vec![Asset::native("uusdc", 100u128)].into(), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "addr1".to_string(),
+                    amount: vec![coin(50, "uusdc")],
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "staking".to_string(),
+                    msg: to_json_binary(&"distribute").unwrap(),
+                    funds: vec![coin(50, "uusdc")],
+                }),
+            ]
+        );
+
+        // Contract addresses are validated too
+        let fee_config = super::FeeConfig {
+            fee_rate: Decimal::percent(10),
+            fee_recipients: vec![(
+                FeeRecipient::Contract {
+                    address: "STAKING".to_string(),
+                    msg: to_json_binary(&"distribute").unwrap(),
+                },
+                Decimal::one().into(),
+            )],
+            ..Default::default()
+        };
+        assert!(matches!(
+            fee_config.check(&deps.as_ref()).unwrap_err(),
+            FeeConfigError::InvalidAddress { address, .. } if address == "STAKING"
+        ));
+    }
 }
@@ -0,0 +1,68 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+use cw_address_like::AddressLike;
+
+use crate::{FeeConfig, FeeConfigError, Weight};
+
+#[cw_serde]
+#[derive(Default)]
+/// Protocol specific limits that a fee config must stay within, on top of the
+/// checks that are always performed by `FeeConfig::check`. Limits that are not
+/// set are not enforced.
+pub struct FeeConfigLimits {
+    /// The highest fee rate that may be configured. Applies to `fee_rate`, the
+    /// rate overrides and the rates of all fee tiers.
+    pub max_fee_rate: Option<Decimal>,
+    /// The largest number of fee recipients.
+    pub max_recipients: Option<u32>,
+    /// Every recipient percentage must be a multiple of this value, e.g.
+    /// `Decimal::percent(1)` to only allow whole percentages. Must be greater
+    /// than zero. Share weights are not affected.
+    pub weight_granularity: Option<Decimal>,
+}
+
+impl FeeConfigLimits {
+    /// Validates that the fee config stays within the limits. Returns an error
+    /// if the limits themselves are invalid, i.e. the weight granularity is
+    /// zero.
+    pub fn validate<T: AddressLike>(&self, config: &FeeConfig<T>) -> Result<(), FeeConfigError> {
+        if self.weight_granularity == Some(Decimal::zero()) {
+            return Err(FeeConfigError::ZeroWeightGranularity);
+        }
+        if let Some(max) = self.max_fee_rate {
+            let rates = std::iter::once(&config.fee_rate)
+                .chain(config.fee_rate_overrides.iter().map(|(_, rate)| rate))
+                .chain(
+                    config
+                        .fee_tiers
+                        .iter()
+                        .flat_map(|(_, tiers)| tiers.tiers.iter().map(|tier| &tier.rate)),
+                );
+            if let Some(rate) = rates.into_iter().find(|rate| **rate > max) {
+                return Err(FeeConfigError::FeeRateAboveLimit { rate: *rate, max });
+            }
+        }
+        if let Some(max) = self.max_recipients {
+            let count = config.fee_recipients.len();
+            if count > max as usize {
+                return Err(FeeConfigError::TooManyRecipients { count, max });
+            }
+        }
+        if let Some(granularity) = self.weight_granularity {
+            for (recipient, weight) in &config.fee_recipients {
+                // Shares are whole numbers, so only percentages are checked
+                let Weight::Percent(weight) = weight else {
+                    continue;
+                };
+                if !weight.checked_rem(granularity)?.is_zero() {
+                    return Err(FeeConfigError::WeightGranularity {
+                        recipient: recipient.to_string(),
+                        weight: *weight,
+                        granularity,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
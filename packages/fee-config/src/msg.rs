@@ -0,0 +1,428 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal};
+use cw_asset::AssetUnchecked;
+
+use crate::{FeeBreakdown, FeeConfig, FeeConfigUpdate, FeeRecipient, Weight};
+
+#[cw_serde]
+/// Execute messages for managing a stored fee config. Meant to be embedded as
+/// a variant of a contract's own `ExecuteMsg`.
+pub enum FeeConfigExecuteMsg {
+    /// Applies a partial update to the fee config.
+    UpdateFeeConfig { update: FeeConfigUpdate },
+    /// Adds a fee recipient with the given weight.
+    AddFeeRecipient {
+        recipient: FeeRecipient<String>,
+        weight: Weight,
+    },
+    /// Removes a fee recipient.
+    RemoveFeeRecipient { recipient: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+/// Query messages for a stored fee config. Meant to be embedded as a variant
+/// of a contract's own `QueryMsg`.
+pub enum FeeConfigQueryMsg {
+    /// Returns the fee config.
+    #[returns(FeeConfig<Addr>)]
+    FeeConfig {},
+    /// Returns the effective fraction of the fee that a recipient receives.
+    /// Recipients without an address are identified by their `Display` form,
+    /// e.g. `burn`.
+    #[returns(Decimal)]
+    RecipientWeight { recipient: String },
+    /// Returns how the fee config would split the given assets, optionally for
+    /// a specific payer who may be exempt from fees.
+    #[returns(FeeBreakdown)]
+    SimulateFee {
+        assets: Vec<AssetUnchecked>,
+        payer: Option<String>,
+    },
+}
+
+#[cfg(feature = "storage")]
+pub use handlers::*;
+
+#[cfg(feature = "storage")]
+mod handlers {
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    };
+    use cw_asset::{Asset, AssetList, AssetUnchecked};
+
+    use super::{FeeConfigExecuteMsg, FeeConfigQueryMsg};
+    use crate::{
+        check_asset_info, validate_addr, FeeBreakdown, FeeConfig, FeeConfigError, FeeConfigItem,
+        FeeConfigUpdate, FeeRecipient, Weight,
+    };
+
+    /// Handles a `FeeConfigExecuteMsg`. `authorize` is called before anything
+    /// else and should return an error if the sender may not change the fee
+    /// config. The updated fee config is validated with `check`, e.g.
+    /// `FeeConfig::check`, `FeeConfig::check_strict` or a closure that calls
+    /// `FeeConfig::check_with_limits`.
+    pub fn execute_fee_config_msg<A, C, E>(
+        deps: DepsMut,
+        info: &MessageInfo,
+        item: &FeeConfigItem,
+        msg: FeeConfigExecuteMsg,
+        authorize: A,
+        check: C,
+    ) -> Result<Response, E>
+    where
+        A: FnOnce(Deps, &MessageInfo) -> Result<(), E>,
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+        E: From<FeeConfigError>,
+    {
+        authorize(deps.as_ref(), info)?;
+        Ok(match msg {
+            FeeConfigExecuteMsg::UpdateFeeConfig { update } => {
+                execute_update_fee_config(deps, item, update, check)?
+            }
+            FeeConfigExecuteMsg::AddFeeRecipient { recipient, weight } => {
+                execute_add_fee_recipient(deps, item, recipient, weight, check)?
+            }
+            FeeConfigExecuteMsg::RemoveFeeRecipient { recipient } => {
+                execute_remove_fee_recipient(deps, item, recipient, check)?
+            }
+        })
+    }
+
+    /// Applies a partial update to the stored fee config and validates the
+    /// result with `check`. Does not perform any authorization.
+    pub fn execute_update_fee_config<C>(
+        deps: DepsMut,
+        item: &FeeConfigItem,
+        update: FeeConfigUpdate,
+        check: C,
+    ) -> Result<Response, FeeConfigError>
+    where
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+    {
+        save_update(deps, item, &update, check)?;
+        Ok(Response::new().add_attribute("action", "update_fee_config"))
+    }
+
+    /// Adds a fee recipient to the stored fee config and validates the result
+    /// with `check`. Does not perform any authorization.
+    pub fn execute_add_fee_recipient<C>(
+        deps: DepsMut,
+        item: &FeeConfigItem,
+        recipient: FeeRecipient<String>,
+        weight: Weight,
+        check: C,
+    ) -> Result<Response, FeeConfigError>
+    where
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+    {
+        let label = recipient.to_string();
+        let update = FeeConfigUpdate {
+            add_recipients: vec![(recipient, weight)],
+            ..Default::default()
+        };
+        save_update(deps, item, &update, check)?;
+        Ok(Response::new()
+            .add_attribute("action", "add_fee_recipient")
+            .add_attribute("recipient", label))
+    }
+
+    /// Removes a fee recipient from the stored fee config and validates the
+    /// result with `check`. Does not perform any authorization.
+    pub fn execute_remove_fee_recipient<C>(
+        deps: DepsMut,
+        item: &FeeConfigItem,
+        recipient: String,
+        check: C,
+    ) -> Result<Response, FeeConfigError>
+    where
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+    {
+        let update = FeeConfigUpdate {
+            remove_recipients: vec![recipient.clone()],
+            ..Default::default()
+        };
+        save_update(deps, item, &update, check)?;
+        Ok(Response::new()
+            .add_attribute("action", "remove_fee_recipient")
+            .add_attribute("recipient", recipient))
+    }
+
+    /// Applies an update to the stored fee config and saves the result after
+    /// validating it with `check`.
+    fn save_update<C>(
+        deps: DepsMut,
+        item: &FeeConfigItem,
+        update: &FeeConfigUpdate,
+        check: C,
+    ) -> Result<(), FeeConfigError>
+    where
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+    {
+        let config = item.load_or_default(deps.storage)?;
+        let updated = config.apply_update_with(update, &deps.as_ref(), check)?;
+        item.item().save(deps.storage, &updated)?;
+        Ok(())
+    }
+
+    /// Handles a `FeeConfigQueryMsg`.
+    pub fn query_fee_config_msg(
+        deps: Deps,
+        env: &Env,
+        item: &FeeConfigItem,
+        msg: FeeConfigQueryMsg,
+    ) -> Result<Binary, FeeConfigError> {
+        Ok(match msg {
+            FeeConfigQueryMsg::FeeConfig {} => to_json_binary(&query_fee_config(deps, item)?),
+            FeeConfigQueryMsg::RecipientWeight { recipient } => {
+                to_json_binary(&query_recipient_weight(deps, item, recipient)?)
+            }
+            FeeConfigQueryMsg::SimulateFee { assets, payer } => {
+                to_json_binary(&query_simulate_fee(deps, env, item, assets, payer)?)
+            }
+        }?)
+    }
+
+    /// Returns the stored fee config, or the default config if none has been
+    /// saved.
+    pub fn query_fee_config(
+        deps: Deps,
+        item: &FeeConfigItem,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        item.load_or_default(deps.storage)
+    }
+
+    /// Returns the effective fraction of the fee that a recipient receives.
+    pub fn query_recipient_weight(
+        deps: Deps,
+        item: &FeeConfigItem,
+        recipient: String,
+    ) -> Result<Decimal, FeeConfigError> {
+        let config = item.load_or_default(deps.storage)?;
+        // Recipients without an address are identified by their `Display`
+        // form, e.g. `burn`
+        if let Some((other, _)) = config
+            .fee_recipients
+            .iter()
+            .find(|(other, _)| other.address().is_none() && other.to_string() == recipient)
+        {
+            return Ok(config.fee_recipient_weight(other));
+        }
+        let recipient = validate_addr(&recipient, &deps)?;
+        Ok(config.recipient_weight(&recipient))
+    }
+
+    /// Returns how the stored fee config would split the given assets.
+    pub fn query_simulate_fee(
+        deps: Deps,
+        env: &Env,
+        item: &FeeConfigItem,
+        assets: Vec<AssetUnchecked>,
+        payer: Option<String>,
+    ) -> Result<FeeBreakdown, FeeConfigError> {
+        let assets: AssetList = assets
+            .iter()
+            .map(|asset| {
+                Ok(Asset::new(
+                    check_asset_info(&asset.info, &deps)?,
+                    asset.amount,
+                ))
+            })
+            .collect::<Result<Vec<_>, FeeConfigError>>()?
+            .into();
+        let config = item.load_or_default(deps.storage)?;
+        match payer {
+            Some(payer) => {
+                let payer = validate_addr(&payer, &deps)?;
+                config.fee_breakdown_for_payer(&payer, &assets, env)
+            }
+            None => config.fee_breakdown(&assets, env),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "storage"))]
+pub mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_json, Addr, Decimal, Deps, MessageInfo, StdError, Uint128};
+    use cw_asset::{Asset, AssetInfo, AssetUnchecked};
+
+    use super::{
+        execute_fee_config_msg, query_fee_config_msg, FeeConfigExecuteMsg, FeeConfigQueryMsg,
+    };
+    use crate::{
+        FeeBreakdown, FeeConfig, FeeConfigError, FeeConfigItem, FeeConfigLimits, FeeConfigUpdate,
+        Weight,
+    };
+
+    const FEE_CONFIG: FeeConfigItem = FeeConfigItem::new("fee_config");
+
+    fn only_admin(_deps: Deps, info: &MessageInfo) -> Result<(), StdError> {
+        if info.sender != "admin" {
+            return Err(StdError::generic_err("Unauthorized"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn execute_and_query() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        FEE_CONFIG
+            .save_checked(
+                deps.as_mut(),
+                &FeeConfig::new_with_shares(Decimal::percent(10), &[("addr1".to_string(), 1)]),
+            )
+            .unwrap();
+
+        // Only the admin may change the fee config
+        let msg = FeeConfigExecuteMsg::AddFeeRecipient {
+            recipient: "addr2".to_string().into(),
+            weight: Weight::Shares(3),
+        };
+        let err = execute_fee_config_msg(
+            deps.as_mut(),
+            &mock_info("user", &[]),
+            &FEE_CONFIG,
+            msg.clone(),
+            only_admin,
+            FeeConfig::check,
+        )
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("Unauthorized"));
+
+        execute_fee_config_msg(
+            deps.as_mut(),
+            &mock_info("admin", &[]),
+            &FEE_CONFIG,
+            msg,
+            only_admin,
+            FeeConfig::check,
+        )
+        .unwrap();
+
+        let weight: Decimal = from_json(
+            query_fee_config_msg(
+                deps.as_ref(),
+                &env,
+                &FEE_CONFIG,
+                FeeConfigQueryMsg::RecipientWeight {
+                    recipient: "addr2".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(weight, Decimal::percent(75));
+
+        let breakdown: FeeBreakdown = from_json(
+            query_fee_config_msg(
+                deps.as_ref(),
+                &env,
+                &FEE_CONFIG,
+                FeeConfigQueryMsg::SimulateFee {
+                    assets: vec![AssetUnchecked::native("uusdc", 1000u128)],
+                    payer: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            breakdown.recipient_amounts(&Addr::unchecked("addr2")),
+            vec![Asset::native("uusdc", 75u128)].into()
+        );
+
+        execute_fee_config_msg(
+            deps.as_mut(),
+            &mock_info("admin", &[]),
+            &FEE_CONFIG,
+            FeeConfigExecuteMsg::RemoveFeeRecipient {
+                recipient: "addr1".to_string(),
+            },
+            only_admin,
+            FeeConfig::check,
+        )
+        .unwrap();
+        let config: FeeConfig<Addr> = from_json(
+            query_fee_config_msg(
+                deps.as_ref(),
+                &env,
+                &FEE_CONFIG,
+                FeeConfigQueryMsg::FeeConfig {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.fee_recipients,
+            vec![(Addr::unchecked("addr2").into(), Weight::Shares(3))]
+        );
+        assert_eq!(
+            config
+                .fee_amount(&Asset::new(AssetInfo::native("uusdc"), Uint128::new(1000)))
+                .unwrap(),
+            Uint128::new(100)
+        );
+    }
+
+    #[test]
+    fn execute_validates_result() {
+        let mut deps = mock_dependencies();
+        FEE_CONFIG
+            .save_checked(
+                deps.as_mut(),
+                &FeeConfig::new(
+                    Decimal::percent(10),
+                    &[("addr1".to_string(), Decimal::one())],
+                ),
+            )
+            .unwrap();
+
+        // Removing the only recipient of a config that charges fees is invalid
+        let err = execute_fee_config_msg(
+            deps.as_mut(),
+            &mock_info("admin", &[]),
+            &FEE_CONFIG,
+            FeeConfigExecuteMsg::RemoveFeeRecipient {
+                recipient: "addr1".to_string(),
+            },
+            |_, _| Ok::<_, FeeConfigError>(()),
+            FeeConfig::check,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::zero()
+            }
+        );
+
+        // Contracts can enforce their own limits on every update
+        let limits = FeeConfigLimits {
+            max_fee_rate: Some(Decimal::percent(15)),
+            ..Default::default()
+        };
+        let err = execute_fee_config_msg(
+            deps.as_mut(),
+            &mock_info("admin", &[]),
+            &FEE_CONFIG,
+            FeeConfigExecuteMsg::UpdateFeeConfig {
+                update: FeeConfigUpdate {
+                    fee_rate: Some(Decimal::percent(20)),
+                    ..Default::default()
+                },
+            },
+            |_, _| Ok::<_, FeeConfigError>(()),
+            |config: &FeeConfig<String>, deps: &Deps| config.check_with_limits(deps, &limits),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            FeeConfigError::FeeRateAboveLimit {
+                rate: Decimal::percent(20),
+                max: Decimal::percent(15)
+            }
+        );
+    }
+}
@@ -0,0 +1,731 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::schemars::{self, JsonSchema};
+#[cfg(feature = "community-pool")]
+use cosmwasm_std::DistributionMsg;
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, Coins, CosmosMsg, Deps, Env,
+    WasmMsg,
+};
+#[cfg(feature = "ibc")]
+use cosmwasm_std::{IbcMsg, IbcTimeout};
+use cw20::Cw20ExecuteMsg;
+use cw_address_like::AddressLike;
+use cw_asset::{Asset, AssetInfo, AssetList};
+use serde::{Deserialize, Serialize};
+
+use crate::{canonicalize_addr, validate_addr, FeeConfigError};
+
+/// A recipient of fees.
+///
+/// A plain address is serialized as a string, which keeps configs that were
+/// created before the other kinds of recipients existed deserializable. The
+/// other kinds are serialized as externally tagged objects, e.g.
+/// `{"contract":{"address":"...","msg":"..."}}`.
+///
+/// Some variants only exist when a feature is enabled. Since features are
+/// additive, the enum is non-exhaustive, so that matches in other crates keep
+/// compiling when another crate enables a feature.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+#[serde(
+    from = "FeeRecipientRepr<T>",
+    into = "FeeRecipientRepr<T>",
+    bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>")
+)]
+pub enum FeeRecipient<T: AddressLike> {
+    /// An address that receives the fees with a plain bank send or cw20
+    /// transfer.
+    Address(T),
+    /// A contract that is notified when it receives fees. Native fees are
+    /// sent along with a `WasmMsg::Execute` of `msg`, and cw20 fees are sent
+    /// with a `Cw20ExecuteMsg::Send` that carries `msg` as its hook message.
+    Contract { address: T, msg: Binary },
+    /// An address on another chain that receives native fees with an ICS-20
+    /// `IbcMsg::Transfer` over `channel_id`. The transfer times out
+    /// `timeout_seconds` after the block in which it is sent. cw20 fees can't
+    /// be sent to IBC recipients.
+    #[cfg(feature = "ibc")]
+    Ibc {
+        channel_id: String,
+        address: String,
+        timeout_seconds: u64,
+    },
+    /// Burns the fees: native fees with `BankMsg::Burn` and cw20 fees with
+    /// `Cw20ExecuteMsg::Burn`. Serialized as `{"burn":{}}`.
+    Burn,
+    /// The chain's community pool, which receives native fees with
+    /// `DistributionMsg::FundCommunityPool`. The community pool only accepts
+    /// native assets, so cw20 fees are transferred to `cw20_fallback`
+    /// instead. Without a fallback, the community pool's share of cw20 fees is
+    /// retained by the contract.
+    #[cfg(feature = "community-pool")]
+    CommunityPool { cw20_fallback: Option<T> },
+}
+
+/// Identifies a fee recipient when comparing it with other recipients.
+/// Recipients with an address on this chain are identified by the canonical
+/// form of the address, other recipients by their `Display` form.
+#[derive(Debug, PartialEq)]
+pub(crate) enum RecipientId {
+    Local(CanonicalAddr),
+    Other(String),
+}
+
+impl<T: AddressLike> FeeRecipient<T> {
+    /// Returns the address of the recipient on this chain, or `None` for
+    /// recipients that don't have one.
+    pub fn address(&self) -> Option<&T> {
+        match self {
+            FeeRecipient::Address(address) => Some(address),
+            FeeRecipient::Contract { address, .. } => Some(address),
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc { .. } => None,
+            FeeRecipient::Burn => None,
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { .. } => None,
+        }
+    }
+
+    /// Replaces the address of the recipient, or the cw20 fallback address of
+    /// a community pool recipient, using `f`, keeping everything else.
+    pub(crate) fn try_map_address<U: AddressLike, E>(
+        &self,
+        f: impl FnOnce(&T) -> Result<U, E>,
+    ) -> Result<FeeRecipient<U>, E> {
+        Ok(match self {
+            FeeRecipient::Address(address) => FeeRecipient::Address(f(address)?),
+            FeeRecipient::Contract { address, msg } => FeeRecipient::Contract {
+                address: f(address)?,
+                msg: msg.clone(),
+            },
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            } => FeeRecipient::Ibc {
+                channel_id: channel_id.clone(),
+                address: address.clone(),
+                timeout_seconds: *timeout_seconds,
+            },
+            FeeRecipient::Burn => FeeRecipient::Burn,
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { cw20_fallback } => FeeRecipient::CommunityPool {
+                cw20_fallback: cw20_fallback.as_ref().map(f).transpose()?,
+            },
+        })
+    }
+
+    /// Returns whether fees in the specified asset can be sent to the
+    /// recipient. IBC recipients and community pool recipients without a cw20
+    /// fallback can only receive native assets.
+    #[cfg_attr(
+        not(any(feature = "ibc", feature = "community-pool")),
+        allow(unused_variables)
+    )]
+    pub fn can_receive(&self, info: &AssetInfo) -> bool {
+        match self {
+            FeeRecipient::Address(_) | FeeRecipient::Contract { .. } | FeeRecipient::Burn => true,
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc { .. } => matches!(info, AssetInfo::Native(_)),
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { cw20_fallback } => {
+                cw20_fallback.is_some() || matches!(info, AssetInfo::Native(_))
+            }
+        }
+    }
+
+    /// Returns the `RecipientId` of the recipient.
+    pub(crate) fn id(&self, deps: &Deps) -> Result<RecipientId, FeeConfigError> {
+        Ok(match self.address() {
+            Some(address) => RecipientId::Local(canonicalize_addr(&address.to_string(), deps)?),
+            None => RecipientId::Other(self.to_string()),
+        })
+    }
+
+    /// Returns whether two recipients are the same. Recipients with an address
+    /// on this chain are compared by their address, so a contract recipient
+    /// is the same as a plain address recipient with the same address.
+    pub(crate) fn is_same(&self, other: &FeeRecipient<T>) -> bool {
+        match (self.address(), other.address()) {
+            (Some(address), Some(other)) => address == other,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl FeeRecipient<String> {
+    /// Validates the recipient and returns a `FeeRecipient<Addr>`.
+    pub fn check(&self, deps: &Deps) -> Result<FeeRecipient<Addr>, FeeConfigError> {
+        #[cfg(feature = "ibc")]
+        if let FeeRecipient::Ibc {
+            channel_id,
+            address,
+            timeout_seconds,
+        } = self
+        {
+            check_ibc_recipient(channel_id, address, *timeout_seconds)?;
+        }
+        self.try_map_address(|address| validate_addr(address, deps))
+    }
+}
+
+/// Validates the channel, remote address and timeout of an IBC recipient.
+/// The remote address must be a lowercase bech32 string.
+#[cfg(feature = "ibc")]
+fn check_ibc_recipient(
+    channel_id: &str,
+    address: &str,
+    timeout_seconds: u64,
+) -> Result<(), FeeConfigError> {
+    let is_channel_id = channel_id
+        .strip_prefix("channel-")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+    if !is_channel_id {
+        return Err(FeeConfigError::InvalidChannelId {
+            channel_id: channel_id.to_string(),
+        });
+    }
+    let invalid_address = |reason: String| FeeConfigError::InvalidRemoteAddress {
+        address: address.to_string(),
+        reason,
+    };
+    if address.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(invalid_address("must be lowercase".to_string()));
+    }
+    bech32::decode(address).map_err(|e| invalid_address(e.to_string()))?;
+    if timeout_seconds == 0 {
+        return Err(FeeConfigError::ZeroIbcTimeout);
+    }
+    Ok(())
+}
+
+impl FeeRecipient<Addr> {
+    /// Creates messages to send an `AssetList` of fees to the recipient.
+    /// `env` is used to calculate the timeout of IBC transfers.
+    ///
+    /// Native fees for an address are combined into a single `BankMsg::Send`
+    /// with the coins sorted by denom, followed by one transfer per cw20
+    /// asset.
+    #[cfg_attr(not(feature = "ibc"), allow(unused_variables))]
+    pub fn transfer_msgs(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        let to_err = |source| FeeConfigError::TransferMsgs {
+            assets: assets.clone(),
+            source,
+        };
+        match self {
+            FeeRecipient::Address(address) => combined_transfer_msgs(
+                assets,
+                |amount| {
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: address.to_string(),
+                        amount,
+                    })
+                },
+                |asset| asset.transfer_msg(address).map_err(to_err),
+            ),
+            FeeRecipient::Contract { address, msg } => combined_transfer_msgs(
+                assets,
+                |funds| {
+                    CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: address.to_string(),
+                        msg: msg.clone(),
+                        funds,
+                    })
+                },
+                |asset| asset.send_msg(address, msg.clone()).map_err(to_err),
+            ),
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            } => assets
+                .into_iter()
+                .map(|asset| match &asset.info {
+                    AssetInfo::Native(denom) => Ok(CosmosMsg::Ibc(IbcMsg::Transfer {
+                        channel_id: channel_id.clone(),
+                        to_address: address.clone(),
+                        amount: Coin::new(asset.amount.u128(), denom),
+                        timeout: IbcTimeout::with_timestamp(
+                            env.block.time.plus_seconds(*timeout_seconds),
+                        ),
+                    })),
+                    _ => Err(FeeConfigError::UnsupportedAsset {
+                        recipient: self.to_string(),
+                        asset: asset.info.clone(),
+                    }),
+                })
+                .collect(),
+            FeeRecipient::Burn => combined_transfer_msgs(
+                assets,
+                |amount| CosmosMsg::Bank(BankMsg::Burn { amount }),
+                |asset| {
+                    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: asset.info.inner(),
+                        msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                            amount: asset.amount,
+                        })?,
+                        funds: vec![],
+                    }))
+                },
+            ),
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { cw20_fallback } => combined_transfer_msgs(
+                assets,
+                |amount| CosmosMsg::Distribution(DistributionMsg::FundCommunityPool { amount }),
+                |asset| match cw20_fallback {
+                    Some(fallback) => asset.transfer_msg(fallback).map_err(to_err),
+                    None => Err(FeeConfigError::UnsupportedAsset {
+                        recipient: self.to_string(),
+                        asset: asset.info.clone(),
+                    }),
+                },
+            ),
+        }
+    }
+
+    /// Creates messages to send an `AssetList` of fees to the recipient like
+    /// `transfer_msgs`, but with one message per asset for addresses, in the
+    /// order of `assets`. The other kinds of recipients are unaffected.
+    pub fn transfer_msgs_per_asset(
+        &self,
+        assets: &AssetList,
+        env: &Env,
+    ) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+        match self {
+            FeeRecipient::Address(address) => {
+                assets
+                    .transfer_msgs(address)
+                    .map_err(|source| FeeConfigError::TransferMsgs {
+                        assets: assets.clone(),
+                        source,
+                    })
+            }
+            _ => self.transfer_msgs(assets, env),
+        }
+    }
+}
+
+/// Creates a single message for all native assets with `native_msg`, with the
+/// coins sorted by denom, followed by one message per cw20 asset created with
+/// `cw20_msg`.
+fn combined_transfer_msgs(
+    assets: &AssetList,
+    native_msg: impl FnOnce(Vec<Coin>) -> CosmosMsg,
+    mut cw20_msg: impl FnMut(&Asset) -> Result<CosmosMsg, FeeConfigError>,
+) -> Result<Vec<CosmosMsg>, FeeConfigError> {
+    let mut coins = Coins::default();
+    let mut msgs = vec![];
+    for asset in assets {
+        match &asset.info {
+            AssetInfo::Native(denom) => coins.add(Coin::new(asset.amount.u128(), denom))?,
+            _ => msgs.push(cw20_msg(asset)?),
+        }
+    }
+    if !coins.is_empty() {
+        msgs.insert(0, native_msg(coins.into_vec()));
+    }
+    Ok(msgs)
+}
+
+/// Formats a recipient with an address on this chain as its address, an IBC
+/// recipient as `ibc:<channel_id>:<address>`, a burn as `burn` and the
+/// community pool as `community_pool`.
+impl<T: AddressLike> fmt::Display for FeeRecipient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeeRecipient::Address(address) => write!(f, "{address}"),
+            FeeRecipient::Contract { address, .. } => write!(f, "{address}"),
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc {
+                channel_id,
+                address,
+                ..
+            } => write!(f, "ibc:{channel_id}:{address}"),
+            FeeRecipient::Burn => write!(f, "burn"),
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { .. } => write!(f, "community_pool"),
+        }
+    }
+}
+
+impl<T: AddressLike> From<T> for FeeRecipient<T> {
+    fn from(address: T) -> Self {
+        FeeRecipient::Address(address)
+    }
+}
+
+impl From<FeeRecipient<Addr>> for FeeRecipient<String> {
+    fn from(value: FeeRecipient<Addr>) -> Self {
+        match value {
+            FeeRecipient::Address(address) => FeeRecipient::Address(address.into()),
+            FeeRecipient::Contract { address, msg } => FeeRecipient::Contract {
+                address: address.into(),
+                msg,
+            },
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            } => FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            },
+            FeeRecipient::Burn => FeeRecipient::Burn,
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { cw20_fallback } => FeeRecipient::CommunityPool {
+                cw20_fallback: cw20_fallback.map(Into::into),
+            },
+        }
+    }
+}
+
+// The schema of `FeeRecipient` is the schema of its serialized form.
+impl<T: AddressLike + JsonSchema> JsonSchema for FeeRecipient<T> {
+    fn schema_name() -> String {
+        format!("FeeRecipient_for_{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        FeeRecipientRepr::<T>::json_schema(gen)
+    }
+}
+
+/// The serialized form of `FeeRecipient`: either a plain address or one of
+/// the tagged kinds of recipients.
+#[cw_serde]
+#[serde(untagged)]
+enum FeeRecipientRepr<T: AddressLike> {
+    Address(T),
+    Tagged(TaggedFeeRecipient<T>),
+}
+
+#[cw_serde]
+enum TaggedFeeRecipient<T: AddressLike> {
+    Contract {
+        address: T,
+        msg: Binary,
+    },
+    #[cfg(feature = "ibc")]
+    Ibc {
+        channel_id: String,
+        address: String,
+        timeout_seconds: u64,
+    },
+    Burn {},
+    #[cfg(feature = "community-pool")]
+    CommunityPool {
+        cw20_fallback: Option<T>,
+    },
+}
+
+impl<T: AddressLike> From<FeeRecipientRepr<T>> for FeeRecipient<T> {
+    fn from(repr: FeeRecipientRepr<T>) -> Self {
+        match repr {
+            FeeRecipientRepr::Address(address) => FeeRecipient::Address(address),
+            FeeRecipientRepr::Tagged(TaggedFeeRecipient::Contract { address, msg }) => {
+                FeeRecipient::Contract { address, msg }
+            }
+            #[cfg(feature = "ibc")]
+            FeeRecipientRepr::Tagged(TaggedFeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            }) => FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            },
+            FeeRecipientRepr::Tagged(TaggedFeeRecipient::Burn {}) => FeeRecipient::Burn,
+            #[cfg(feature = "community-pool")]
+            FeeRecipientRepr::Tagged(TaggedFeeRecipient::CommunityPool { cw20_fallback }) => {
+                FeeRecipient::CommunityPool { cw20_fallback }
+            }
+        }
+    }
+}
+
+impl<T: AddressLike> From<FeeRecipient<T>> for FeeRecipientRepr<T> {
+    fn from(recipient: FeeRecipient<T>) -> Self {
+        match recipient {
+            FeeRecipient::Address(address) => FeeRecipientRepr::Address(address),
+            FeeRecipient::Contract { address, msg } => {
+                FeeRecipientRepr::Tagged(TaggedFeeRecipient::Contract { address, msg })
+            }
+            #[cfg(feature = "ibc")]
+            FeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            } => FeeRecipientRepr::Tagged(TaggedFeeRecipient::Ibc {
+                channel_id,
+                address,
+                timeout_seconds,
+            }),
+            FeeRecipient::Burn => FeeRecipientRepr::Tagged(TaggedFeeRecipient::Burn {}),
+            #[cfg(feature = "community-pool")]
+            FeeRecipient::CommunityPool { cw20_fallback } => {
+                FeeRecipientRepr::Tagged(TaggedFeeRecipient::CommunityPool { cw20_fallback })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{
+        coin, from_json, to_json_binary, Addr, Binary, CosmosMsg, Uint128, WasmMsg,
+    };
+    use cw20::Cw20ExecuteMsg;
+    use cw_asset::{Asset, AssetList};
+
+    use super::FeeRecipient;
+
+    #[test]
+    fn fee_recipient_serialization() {
+        let recipient: FeeRecipient<String> = from_json(br#""addr1""#).unwrap();
+        assert_eq!(recipient, FeeRecipient::Address("addr1".to_string()));
+
+        let recipient = FeeRecipient::Contract {
+            address: "addr1".to_string(),
+            msg: Binary::from(b"{}"),
+        };
+        let json = to_json_binary(&recipient).unwrap();
+        assert_eq!(
+            json.to_vec(),
+            br#"{"contract":{"address":"addr1","msg":"e30="}}"#.to_vec()
+        );
+        assert_eq!(from_json::<FeeRecipient<String>>(&json).unwrap(), recipient);
+    }
+
+    #[test]
+    fn burn_recipient_serialization() {
+        let json = to_json_binary(&FeeRecipient::<String>::Burn).unwrap();
+        assert_eq!(json.to_vec(), br#"{"burn":{}}"#.to_vec());
+        assert_eq!(
+            from_json::<FeeRecipient<String>>(&json).unwrap(),
+            FeeRecipient::Burn
+        );
+        // A plain address can't be confused with a burn
+        assert_eq!(
+            from_json::<FeeRecipient<String>>(br#""burn""#).unwrap(),
+            FeeRecipient::Address("burn".to_string())
+        );
+    }
+
+    #[test]
+    fn contract_recipient_transfer_msgs() {
+        let recipient = FeeRecipient::Contract {
+            address: Addr::unchecked("staking"),
+            msg: Binary::from(b"{\"distribute\":{}}"),
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 10u128),
+            Asset::cw20(Addr::unchecked("token"), 5u128),
+            Asset::native("uatom", 20u128),
+        ]
+        .into();
+
+        let msgs = recipient.transfer_msgs(&assets, &mock_env()).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "staking".to_string(),
+                    msg: Binary::from(b"{\"distribute\":{}}"),
+                    funds: vec![coin(20, "uatom"), coin(10, "uusdc")],
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "token".to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                        contract: "staking".to_string(),
+                        amount: Uint128::new(5),
+                        msg: Binary::from(b"{\"distribute\":{}}"),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[cfg(feature = "ibc")]
+    #[test]
+    fn ibc_recipient_check() {
+        use cosmwasm_std::testing::mock_dependencies;
+
+        use crate::FeeConfigError;
+
+        let deps = mock_dependencies();
+        let recipient = |channel_id: &str, address: &str, timeout_seconds| FeeRecipient::Ibc {
+            channel_id: channel_id.to_string(),
+            address: address.to_string(),
+            timeout_seconds,
+        };
+        let address = "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t";
+
+        let valid = recipient("channel-0", address, 600);
+        assert_eq!(
+            FeeRecipient::<String>::from(valid.check(&deps.as_ref()).unwrap()),
+            valid
+        );
+        assert_eq!(valid.to_string(), format!("ibc:channel-0:{address}"));
+
+        assert_eq!(
+            recipient("transfer", address, 600)
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::InvalidChannelId {
+                channel_id: "transfer".to_string()
+            }
+        );
+        assert!(matches!(
+            recipient(
+                "channel-0",
+                "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28u",
+                600
+            )
+            .check(&deps.as_ref())
+            .unwrap_err(),
+            FeeConfigError::InvalidRemoteAddress { .. }
+        ));
+        assert!(matches!(
+            recipient("channel-0", &address.to_uppercase(), 600)
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::InvalidRemoteAddress { .. }
+        ));
+        assert_eq!(
+            recipient("channel-0", address, 0)
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::ZeroIbcTimeout
+        );
+
+        let json = to_json_binary(&valid).unwrap();
+        assert_eq!(
+            json.to_vec(),
+            format!(
+                r#"{{"ibc":{{"channel_id":"channel-0","address":"{address}","timeout_seconds":600}}}}"#
+            )
+            .into_bytes()
+        );
+        assert_eq!(from_json::<FeeRecipient<String>>(&json).unwrap(), valid);
+    }
+
+    #[cfg(feature = "ibc")]
+    #[test]
+    fn ibc_recipient_transfer_msgs() {
+        use cosmwasm_std::{IbcMsg, IbcTimeout};
+        use cw_asset::AssetInfo;
+
+        use crate::FeeConfigError;
+
+        let env = mock_env();
+        let recipient = FeeRecipient::Ibc {
+            channel_id: "channel-0".to_string(),
+            address: "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t".to_string(),
+            timeout_seconds: 600,
+        };
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 10u128),
+            Asset::native("uatom", 20u128),
+        ]
+        .into();
+        let transfer = |amount| {
+            CosmosMsg::Ibc(IbcMsg::Transfer {
+                channel_id: "channel-0".to_string(),
+                to_address: "osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysntdz28t".to_string(),
+                amount,
+                timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(600)),
+            })
+        };
+        assert_eq!(
+            recipient.transfer_msgs(&assets, &env).unwrap(),
+            vec![transfer(coin(10, "uusdc")), transfer(coin(20, "uatom"))]
+        );
+
+        // cw20 fees can't be sent over IBC
+        let assets: AssetList = vec![Asset::cw20(Addr::unchecked("token"), 5u128)].into();
+        assert_eq!(
+            recipient.transfer_msgs(&assets, &env).unwrap_err(),
+            FeeConfigError::UnsupportedAsset {
+                recipient: recipient.to_string(),
+                asset: AssetInfo::cw20(Addr::unchecked("token")),
+            }
+        );
+    }
+
+    #[cfg(feature = "community-pool")]
+    #[test]
+    fn community_pool_recipient() {
+        use cosmwasm_std::testing::mock_dependencies;
+        use cosmwasm_std::DistributionMsg;
+        use cw_asset::AssetInfo;
+
+        use crate::FeeConfigError;
+
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let recipient: FeeRecipient<String> =
+            from_json(br#"{"community_pool":{"cw20_fallback":"treasury"}}"#).unwrap();
+        let recipient = recipient.check(&deps.as_ref()).unwrap();
+        assert_eq!(
+            recipient,
+            FeeRecipient::CommunityPool {
+                cw20_fallback: Some(Addr::unchecked("treasury"))
+            }
+        );
+        assert_eq!(recipient.to_string(), "community_pool");
+
+        // Native fees fund the community pool, cw20 fees go to the fallback
+        let assets: AssetList = vec![
+            Asset::native("uusdc", 10u128),
+            Asset::cw20(Addr::unchecked("token"), 5u128),
+            Asset::native("uatom", 20u128),
+        ]
+        .into();
+        assert_eq!(
+            recipient.transfer_msgs(&assets, &env).unwrap(),
+            vec![
+                CosmosMsg::Distribution(DistributionMsg::FundCommunityPool {
+                    amount: vec![coin(20, "uatom"), coin(10, "uusdc")],
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "token".to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: "treasury".to_string(),
+                        amount: Uint128::new(5),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+
+        // Without a fallback, cw20 fees can't be sent
+        let recipient = FeeRecipient::CommunityPool {
+            cw20_fallback: None,
+        };
+        assert!(recipient.can_receive(&AssetInfo::native("uusdc")));
+        assert!(!recipient.can_receive(&AssetInfo::cw20(Addr::unchecked("token"))));
+        assert_eq!(
+            recipient.transfer_msgs(&assets, &env).unwrap_err(),
+            FeeConfigError::UnsupportedAsset {
+                recipient: "community_pool".to_string(),
+                asset: AssetInfo::cw20(Addr::unchecked("token")),
+            }
+        );
+    }
+}
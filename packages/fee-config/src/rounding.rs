@@ -0,0 +1,112 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+
+use crate::FeeConfigError;
+
+#[cw_serde]
+#[derive(Copy, Default)]
+/// Determines how fractional amounts are rounded to whole token units.
+pub enum Rounding {
+    /// Round towards zero.
+    #[default]
+    Floor,
+    /// Round away from zero. When splitting a fee with
+    /// `Distribution::Independent`, the rounded-up shares are handed out in
+    /// order until the fee runs out, so recipients listed last may receive
+    /// less than their weight or nothing at all, e.g. 4 split 1:1:1 gives
+    /// `[2, 2, 0]`. Use `Distribution::LargestRemainder` for a fair split.
+    Ceil,
+    /// Round to the nearest unit, with halves rounded up.
+    HalfUp,
+    /// Round to the nearest unit, with halves rounded to the nearest even
+    /// unit (banker's rounding).
+    HalfEven,
+}
+
+impl Rounding {
+    /// Divides `numerator` by `denominator` and rounds the result.
+    pub fn div(self, numerator: Uint256, denominator: Uint256) -> Result<Uint256, FeeConfigError> {
+        let quotient = numerator.checked_div(denominator)?;
+        let remainder = numerator.checked_rem(denominator)?;
+        let round_up = match self {
+            Rounding::Floor => false,
+            Rounding::Ceil => !remainder.is_zero(),
+            Rounding::HalfUp => remainder >= denominator - remainder,
+            Rounding::HalfEven => {
+                let half = denominator - remainder;
+                remainder > half
+                    || (remainder == half && quotient % Uint256::from(2u8) == Uint256::one())
+            }
+        };
+        Ok(if round_up {
+            quotient + Uint256::one()
+        } else {
+            quotient
+        })
+    }
+
+    /// Calculates `amount * numerator / denominator` and rounds the result.
+    pub fn mul_ratio(
+        self,
+        amount: Uint128,
+        numerator: impl Into<Uint256>,
+        denominator: impl Into<Uint256>,
+    ) -> Result<Uint128, FeeConfigError> {
+        let product = Uint256::from(amount).checked_mul(numerator.into())?;
+        Ok(self.div(product, denominator.into())?.try_into()?)
+    }
+
+    /// Calculates `amount * decimal` and rounds the result.
+    pub fn mul_decimal(self, amount: Uint128, decimal: Decimal) -> Result<Uint128, FeeConfigError> {
+        self.mul_ratio(amount, decimal.atomics(), Decimal::one().atomics())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use super::Rounding;
+
+    #[test]
+    fn mul_decimal_rounds_according_to_mode() {
+        let cases = [
+            // (amount, rate, floor, ceil, half_up, half_even)
+            (149u128, Decimal::percent(1), 1u128, 2u128, 1u128, 1u128),
+            (150, Decimal::percent(1), 1, 2, 2, 2),
+            (250, Decimal::percent(1), 2, 3, 3, 2),
+            (251, Decimal::percent(1), 2, 3, 3, 3),
+            (200, Decimal::percent(1), 2, 2, 2, 2),
+            (1, Decimal::percent(1), 0, 1, 0, 0),
+        ];
+        for (amount, rate, floor, ceil, half_up, half_even) in cases {
+            let amount = Uint128::new(amount);
+            assert_eq!(
+                Rounding::Floor.mul_decimal(amount, rate).unwrap(),
+                Uint128::new(floor)
+            );
+            assert_eq!(
+                Rounding::Ceil.mul_decimal(amount, rate).unwrap(),
+                Uint128::new(ceil)
+            );
+            assert_eq!(
+                Rounding::HalfUp.mul_decimal(amount, rate).unwrap(),
+                Uint128::new(half_up)
+            );
+            assert_eq!(
+                Rounding::HalfEven.mul_decimal(amount, rate).unwrap(),
+                Uint128::new(half_even)
+            );
+        }
+    }
+
+    #[test]
+    fn mul_ratio_handles_amounts_that_overflow_uint128() {
+        assert_eq!(
+            Rounding::Ceil
+                .mul_ratio(Uint128::MAX, Uint128::MAX, Uint128::MAX)
+                .unwrap(),
+            Uint128::MAX
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, Env, Timestamp};
+use cw_address_like::AddressLike;
+
+use crate::{FeeConfig, FeeConfigError};
+
+#[cw_serde]
+/// Fee configs that change over time. Each entry is a fee config along with
+/// the block time or height from which it applies, and applies until the
+/// start of the next entry.
+pub enum FeeSchedule<T: AddressLike> {
+    /// Entries that start at a block time.
+    ByTime(Vec<(Timestamp, FeeConfig<T>)>),
+    /// Entries that start at a block height.
+    ByHeight(Vec<(u64, FeeConfig<T>)>),
+}
+
+impl FeeSchedule<String> {
+    /// Validates that the schedule is non-empty, that the entries are sorted
+    /// by strictly increasing start and that each fee config passes
+    /// `FeeConfig::check`. Returns a `FeeSchedule<Addr>`.
+    pub fn check(&self, deps: &Deps) -> Result<FeeSchedule<Addr>, FeeConfigError> {
+        Ok(match self {
+            FeeSchedule::ByTime(entries) => FeeSchedule::ByTime(check_entries(entries, deps)?),
+            FeeSchedule::ByHeight(entries) => FeeSchedule::ByHeight(check_entries(entries, deps)?),
+        })
+    }
+}
+
+impl FeeSchedule<Addr> {
+    /// Returns the fee config that applies at the current block, i.e. the
+    /// last entry that started at or before `env.block`. Returns `None` before
+    /// the first entry has started.
+    pub fn active_config(&self, env: &Env) -> Option<&FeeConfig<Addr>> {
+        match self {
+            FeeSchedule::ByTime(entries) => active_entry(entries, env.block.time),
+            FeeSchedule::ByHeight(entries) => active_entry(entries, env.block.height),
+        }
+    }
+}
+
+/// Validates the entries of a `FeeSchedule`, see `FeeSchedule::check`.
+fn check_entries<K: Copy + PartialOrd>(
+    entries: &[(K, FeeConfig<String>)],
+    deps: &Deps,
+) -> Result<Vec<(K, FeeConfig<Addr>)>, FeeConfigError> {
+    if entries.is_empty() {
+        return Err(FeeConfigError::EmptyFeeSchedule);
+    }
+    if entries.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(FeeConfigError::FeeScheduleNotIncreasing);
+    }
+    entries
+        .iter()
+        .map(|(start, config)| Ok((*start, config.check(deps)?)))
+        .collect()
+}
+
+/// Returns the fee config of the last entry that started at or before `now`.
+fn active_entry<K: PartialOrd>(
+    entries: &[(K, FeeConfig<Addr>)],
+    now: K,
+) -> Option<&FeeConfig<Addr>> {
+    entries
+        .iter()
+        .rev()
+        .find(|(start, _)| *start <= now)
+        .map(|(_, config)| config)
+}
+
+impl From<FeeSchedule<Addr>> for FeeSchedule<String> {
+    fn from(value: FeeSchedule<Addr>) -> Self {
+        match value {
+            FeeSchedule::ByTime(entries) => FeeSchedule::ByTime(
+                entries
+                    .into_iter()
+                    .map(|(start, config)| (start, config.into()))
+                    .collect(),
+            ),
+            FeeSchedule::ByHeight(entries) => FeeSchedule::ByHeight(
+                entries
+                    .into_iter()
+                    .map(|(start, config)| (start, config.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{Decimal, Timestamp};
+
+    use super::FeeSchedule;
+    use crate::{FeeConfig, FeeConfigError};
+
+    fn fee_config(percent: u64) -> FeeConfig<String> {
+        FeeConfig::new(
+            Decimal::percent(percent),
+            &[("addr1".to_string(), Decimal::one())],
+        )
+    }
+
+    #[test]
+    fn active_config_follows_the_schedule() {
+        let deps = mock_dependencies();
+        let mut env = mock_env();
+        let launch = env.block.time;
+
+        let schedule = FeeSchedule::ByTime(vec![
+            (launch, fee_config(0)),
+            (launch.plus_days(30), fee_config(1)),
+            (launch.plus_days(60), fee_config(2)),
+        ])
+        .check(&deps.as_ref())
+        .unwrap();
+
+        env.block.time = launch.minus_seconds(1);
+        assert_eq!(schedule.active_config(&env), None);
+        for (time, rate) in [
+            (launch, 0),
+            (launch.plus_days(30).minus_seconds(1), 0),
+            (launch.plus_days(30), 1),
+            (launch.plus_days(365), 2),
+        ] {
+            env.block.time = time;
+            assert_eq!(
+                schedule.active_config(&env).unwrap().fee_rate,
+                Decimal::percent(rate)
+            );
+        }
+
+        let schedule = FeeSchedule::ByHeight(vec![(0, fee_config(1)), (1000, fee_config(0))])
+            .check(&deps.as_ref())
+            .unwrap();
+        env.block.height = 999;
+        assert_eq!(
+            schedule.active_config(&env).unwrap().fee_rate,
+            Decimal::percent(1)
+        );
+        env.block.height = 1000;
+        assert_eq!(
+            schedule.active_config(&env).unwrap().fee_rate,
+            Decimal::zero()
+        );
+    }
+
+    #[test]
+    fn check_validates_schedule() {
+        let deps = mock_dependencies();
+
+        assert_eq!(
+            FeeSchedule::<String>::ByHeight(vec![])
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::EmptyFeeSchedule
+        );
+        assert_eq!(
+            FeeSchedule::ByTime(vec![
+                (Timestamp::from_seconds(2), fee_config(1)),
+                (Timestamp::from_seconds(1), fee_config(2)),
+            ])
+            .check(&deps.as_ref())
+            .unwrap_err(),
+            FeeConfigError::FeeScheduleNotIncreasing
+        );
+        assert_eq!(
+            FeeSchedule::ByHeight(vec![(1, fee_config(1)), (1, fee_config(2))])
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::FeeScheduleNotIncreasing
+        );
+        // Each entry must be a valid fee config
+        assert_eq!(
+            FeeSchedule::ByHeight(vec![(1, fee_config(1)), (2, fee_config(101))])
+                .check(&deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::RateTooHigh {
+                rate: Decimal::percent(101)
+            }
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use cosmwasm_std::{Addr, DepsMut, Storage};
+use cw_storage_plus::Item;
+
+use crate::{FeeConfig, FeeConfigError};
+
+/// A wrapper around a cw-storage-plus `Item` that stores a `FeeConfig<Addr>`
+/// and makes sure that only validated configs are saved.
+pub struct FeeConfigItem<'a>(Item<'a, FeeConfig<Addr>>);
+
+impl<'a> FeeConfigItem<'a> {
+    /// Creates a new `FeeConfigItem` stored under the given key.
+    pub const fn new(storage_key: &'a str) -> Self {
+        Self(Item::new(storage_key))
+    }
+
+    /// Returns the underlying `Item`.
+    pub fn item(&self) -> &Item<'a, FeeConfig<Addr>> {
+        &self.0
+    }
+
+    /// Validates the fee config with `FeeConfig::check` and saves it. Returns
+    /// the validated config.
+    pub fn save_checked(
+        &self,
+        deps: DepsMut,
+        config: &FeeConfig<String>,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        let checked = config.check(&deps.as_ref())?;
+        self.0.save(deps.storage, &checked)?;
+        Ok(checked)
+    }
+
+    /// Loads the fee config. Returns an error if no config has been saved.
+    pub fn load(&self, storage: &dyn Storage) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        Ok(self.0.load(storage)?)
+    }
+
+    /// Loads the fee config, or returns `None` if no config has been saved.
+    pub fn may_load(
+        &self,
+        storage: &dyn Storage,
+    ) -> Result<Option<FeeConfig<Addr>>, FeeConfigError> {
+        Ok(self.0.may_load(storage)?)
+    }
+
+    /// Loads the fee config, or returns the default config, which charges no
+    /// fees, if no config has been saved.
+    pub fn load_or_default(
+        &self,
+        storage: &dyn Storage,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        Ok(self.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Loads the fee config, or the default config if none has been saved,
+    /// passes it to `action` and saves the result after validating it with
+    /// `FeeConfig::check`. Returns the validated config.
+    pub fn update<A, E>(&self, deps: DepsMut, action: A) -> Result<FeeConfig<Addr>, E>
+    where
+        A: FnOnce(FeeConfig<String>) -> Result<FeeConfig<String>, E>,
+        E: From<FeeConfigError>,
+    {
+        let config = self.load_or_default(deps.storage)?;
+        let updated = action(config.into())?;
+        Ok(self.save_checked(deps, &updated)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{Addr, Decimal};
+
+    use super::FeeConfigItem;
+    use crate::{FeeConfig, FeeConfigError};
+
+    const FEE_CONFIG: FeeConfigItem = FeeConfigItem::new("fee_config");
+
+    #[test]
+    fn save_checked_and_load() {
+        let mut deps = mock_dependencies();
+
+        assert_eq!(FEE_CONFIG.may_load(&deps.storage).unwrap(), None);
+        assert_eq!(
+            FEE_CONFIG.load_or_default(&deps.storage).unwrap(),
+            FeeConfig::default()
+        );
+        FEE_CONFIG.load(&deps.storage).unwrap_err();
+
+        let config = FeeConfig::new(
+            Decimal::percent(1),
+            &[("addr1".to_string(), Decimal::one())],
+        );
+        let checked = FEE_CONFIG.save_checked(deps.as_mut(), &config).unwrap();
+        assert_eq!(FEE_CONFIG.load(&deps.storage).unwrap(), checked);
+        assert_eq!(FEE_CONFIG.may_load(&deps.storage).unwrap(), Some(checked));
+
+        // Invalid configs are not saved
+        let invalid = FeeConfig::new(Decimal::percent(150), &[]);
+        assert_eq!(
+            FEE_CONFIG
+                .save_checked(deps.as_mut(), &invalid)
+                .unwrap_err(),
+            FeeConfigError::RateTooHigh {
+                rate: Decimal::percent(150)
+            }
+        );
+        assert_eq!(
+            FEE_CONFIG.load(&deps.storage).unwrap().fee_rate,
+            Decimal::percent(1)
+        );
+    }
+
+    #[test]
+    fn update_validates_result() {
+        let mut deps = mock_dependencies();
+
+        let updated = FEE_CONFIG
+            .update(deps.as_mut(), |config| -> Result<_, FeeConfigError> {
+                Ok(FeeConfig {
+                    fee_rate: Decimal::percent(2),
+                    fee_recipients: vec![("addr1".to_string().into(), Decimal::one().into())],
+                    ..config
+                })
+            })
+            .unwrap();
+        assert_eq!(updated.fee_rate, Decimal::percent(2));
+        assert_eq!(
+            updated.recipient_weight(&Addr::unchecked("addr1")),
+            Decimal::one()
+        );
+
+        let err = FEE_CONFIG
+            .update(deps.as_mut(), |config| -> Result<_, FeeConfigError> {
+                Ok(FeeConfig {
+                    fee_recipients: vec![],
+                    ..config
+                })
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::zero()
+            }
+        );
+        assert_eq!(FEE_CONFIG.load(&deps.storage).unwrap(), updated);
+    }
+}
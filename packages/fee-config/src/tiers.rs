@@ -0,0 +1,209 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+
+use crate::{FeeConfigError, Rounding};
+
+#[cw_serde]
+/// A fee rate that applies from a certain amount upwards.
+pub struct FeeTier {
+    /// The amount from which this tier applies.
+    pub threshold: Uint128,
+    /// The fee rate of this tier.
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+#[derive(Copy, Default)]
+/// Determines how the rates of `FeeTiers` are applied to an amount.
+pub enum TierMode {
+    /// Each tier's rate applies only to the part of the amount that falls
+    /// within that tier, like income tax brackets.
+    #[default]
+    Marginal,
+    /// The rate of the tier that the amount falls into applies to the whole
+    /// amount.
+    Flat,
+}
+
+#[cw_serde]
+/// Fee rates that depend on the amount the fee is taken from.
+pub struct FeeTiers {
+    /// How the tier rates are applied.
+    pub mode: TierMode,
+    /// The tiers, sorted by threshold in strictly ascending order. The first
+    /// tier must have a threshold of zero.
+    pub tiers: Vec<FeeTier>,
+}
+
+impl FeeTiers {
+    /// Validates that the tiers are non-empty, start at zero, have strictly
+    /// increasing thresholds and rates no higher than 100%.
+    pub fn validate(&self) -> Result<(), FeeConfigError> {
+        match self.tiers.first() {
+            None => return Err(FeeConfigError::EmptyFeeTiers),
+            Some(first) if !first.threshold.is_zero() => {
+                return Err(FeeConfigError::FirstTierThresholdNotZero)
+            }
+            _ => {}
+        }
+        if self
+            .tiers
+            .windows(2)
+            .any(|pair| pair[0].threshold >= pair[1].threshold)
+        {
+            return Err(FeeConfigError::TierThresholdsNotIncreasing);
+        }
+        if let Some(tier) = self.tiers.iter().find(|tier| tier.rate > Decimal::one()) {
+            return Err(FeeConfigError::RateTooHigh { rate: tier.rate });
+        }
+        Ok(())
+    }
+
+    /// Returns true if any of the tiers has a non-zero rate.
+    pub fn charges_fees(&self) -> bool {
+        self.tiers.iter().any(|tier| !tier.rate.is_zero())
+    }
+
+    /// Returns the index of the tier that `amount` falls into.
+    fn tier_index(&self, amount: Uint128) -> usize {
+        self.tiers
+            .iter()
+            .rposition(|tier| tier.threshold <= amount)
+            .unwrap_or_default()
+    }
+
+    /// Returns the inclusive range of amounts covered by the tier at `index`.
+    fn tier_range(&self, index: usize) -> (Uint128, Uint128) {
+        let end = self
+            .tiers
+            .get(index + 1)
+            .map(|next| next.threshold - Uint128::one())
+            .unwrap_or(Uint128::MAX);
+        (self.tiers[index].threshold, end)
+    }
+
+    /// Calculates the fee that is taken from `amount`.
+    pub fn fee(&self, amount: Uint128, rounding: Rounding) -> Result<Uint128, FeeConfigError> {
+        if self.tiers.is_empty() {
+            return Ok(Uint128::zero());
+        }
+        match self.mode {
+            TierMode::Flat => {
+                rounding.mul_decimal(amount, self.tiers[self.tier_index(amount)].rate)
+            }
+            TierMode::Marginal => {
+                let mut fee = Uint256::zero();
+                for (i, tier) in self.tiers.iter().enumerate() {
+                    if amount <= tier.threshold {
+                        break;
+                    }
+                    let upper = self
+                        .tiers
+                        .get(i + 1)
+                        .map_or(amount, |next| next.threshold.min(amount));
+                    fee +=
+                        Uint256::from(upper - tier.threshold) * Uint256::from(tier.rate.atomics());
+                }
+                Ok(rounding
+                    .div(fee, Uint256::from(Decimal::one().atomics()))?
+                    .try_into()?)
+            }
+        }
+    }
+
+    /// Returns the inclusive ranges of amounts within which the amount
+    /// remaining after fees never decreases as the amount increases. For
+    /// marginal tiers this is the whole range, while for flat tiers it is
+    /// each of the tiers.
+    pub(crate) fn monotonic_ranges(&self) -> Vec<(Uint128, Uint128)> {
+        match self.mode {
+            TierMode::Marginal => vec![(Uint128::zero(), Uint128::MAX)],
+            TierMode::Flat => (0..self.tiers.len()).map(|i| self.tier_range(i)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use super::{FeeTier, FeeTiers, TierMode};
+    use crate::{FeeConfigError, Rounding};
+
+    fn tiers(mode: TierMode) -> FeeTiers {
+        FeeTiers {
+            mode,
+            tiers: vec![
+                FeeTier {
+                    threshold: Uint128::zero(),
+                    rate: Decimal::permille(3),
+                },
+                FeeTier {
+                    threshold: Uint128::new(10_000),
+                    rate: Decimal::permille(2),
+                },
+                FeeTier {
+                    threshold: Uint128::new(100_000),
+                    rate: Decimal::permille(1),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn marginal_fee_works() {
+        let tiers = tiers(TierMode::Marginal);
+        let fee = |amount: u128| tiers.fee(Uint128::new(amount), Rounding::Floor).unwrap();
+        assert_eq!(fee(0), Uint128::zero());
+        assert_eq!(fee(5_000), Uint128::new(15));
+        assert_eq!(fee(10_000), Uint128::new(30));
+        // 30 + 0.2% of 40k
+        assert_eq!(fee(50_000), Uint128::new(110));
+        // 30 + 180 + 0.1% of 100k
+        assert_eq!(fee(200_000), Uint128::new(310));
+    }
+
+    #[test]
+    fn flat_fee_works() {
+        let tiers = tiers(TierMode::Flat);
+        let fee = |amount: u128| tiers.fee(Uint128::new(amount), Rounding::Floor).unwrap();
+        assert_eq!(fee(9_999), Uint128::new(29));
+        assert_eq!(fee(10_000), Uint128::new(20));
+        assert_eq!(fee(50_000), Uint128::new(100));
+        assert_eq!(fee(200_000), Uint128::new(200));
+    }
+
+    #[test]
+    fn validate_works() {
+        tiers(TierMode::Flat).validate().unwrap();
+
+        let mut invalid = tiers(TierMode::Flat);
+        invalid.tiers[0].threshold = Uint128::one();
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            FeeConfigError::FirstTierThresholdNotZero
+        );
+
+        let mut invalid = tiers(TierMode::Flat);
+        invalid.tiers[2].threshold = Uint128::new(10_000);
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            FeeConfigError::TierThresholdsNotIncreasing
+        );
+
+        let mut invalid = tiers(TierMode::Flat);
+        invalid.tiers[1].rate = Decimal::percent(101);
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            FeeConfigError::RateTooHigh {
+                rate: Decimal::percent(101)
+            }
+        );
+
+        let empty = FeeTiers {
+            mode: TierMode::Marginal,
+            tiers: vec![],
+        };
+        assert_eq!(empty.validate().unwrap_err(), FeeConfigError::EmptyFeeTiers);
+    }
+}
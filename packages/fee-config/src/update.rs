@@ -0,0 +1,164 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Deps};
+
+use crate::recipient::RecipientId;
+use crate::{canonicalize_addr, FeeConfig, FeeConfigError, FeeRecipient, Weight};
+
+#[cw_serde]
+#[derive(Default)]
+/// A partial update of a fee config. The operations are applied in the order
+/// of the fields: first the fee rate is set, then recipients are removed, then
+/// new recipients are added and finally the weights of existing recipients
+/// are changed.
+pub struct FeeConfigUpdate {
+    /// The new fee rate, if it should be changed.
+    #[serde(default)]
+    pub fee_rate: Option<Decimal>,
+    /// Fee recipients to remove, identified by their address or, for
+    /// recipients without an address on this chain, their `Display` form.
+    #[serde(default)]
+    pub remove_recipients: Vec<String>,
+    /// Fee recipients to add, along with their weights.
+    #[serde(default)]
+    pub add_recipients: Vec<(FeeRecipient<String>, Weight)>,
+    /// New weights for existing fee recipients.
+    #[serde(default)]
+    pub set_recipient_weight: Vec<(String, Weight)>,
+}
+
+impl FeeConfig<Addr> {
+    /// Applies a `FeeConfigUpdate` and validates the result with the same
+    /// rules as `FeeConfig::check`. Returns an error if a recipient that should
+    /// be removed or reweighted is not part of the config.
+    pub fn apply_update(
+        &self,
+        update: &FeeConfigUpdate,
+        deps: &Deps,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError> {
+        self.apply_update_with(update, deps, FeeConfig::check)
+    }
+
+    /// Applies a `FeeConfigUpdate` like `apply_update`, but validates the
+    /// result with `check` instead, e.g. `FeeConfig::check_strict` or a closure
+    /// that calls `FeeConfig::check_with_limits`.
+    pub fn apply_update_with<C>(
+        &self,
+        update: &FeeConfigUpdate,
+        deps: &Deps,
+        check: C,
+    ) -> Result<FeeConfig<Addr>, FeeConfigError>
+    where
+        C: FnOnce(&FeeConfig<String>, &Deps) -> Result<FeeConfig<Addr>, FeeConfigError>,
+    {
+        let mut config: FeeConfig<String> = self.clone().into();
+        if let Some(fee_rate) = update.fee_rate {
+            config.fee_rate = fee_rate;
+        }
+        for recipient in &update.remove_recipients {
+            let i = find_recipient(&config, recipient, deps)?;
+            config.fee_recipients.remove(i);
+        }
+        config
+            .fee_recipients
+            .extend(update.add_recipients.iter().cloned());
+        for (recipient, weight) in &update.set_recipient_weight {
+            let i = find_recipient(&config, recipient, deps)?;
+            config.fee_recipients[i].1 = *weight;
+        }
+        check(&config, deps)
+    }
+}
+
+/// Returns the index of a fee recipient. Addresses are compared in their
+/// canonical form, recipients without an address on this chain by their
+/// `Display` form, e.g. `ibc:channel-0:cosmos1...`.
+fn find_recipient(
+    config: &FeeConfig<String>,
+    recipient: &str,
+    deps: &Deps,
+) -> Result<usize, FeeConfigError> {
+    let canonical = canonicalize_addr(recipient, deps).ok();
+    for (i, (other, _)) in config.fee_recipients.iter().enumerate() {
+        let found = match other.id(deps)? {
+            RecipientId::Local(other) => canonical.as_ref() == Some(&other),
+            RecipientId::Other(other) => other == recipient,
+        };
+        if found {
+            return Ok(i);
+        }
+    }
+    Err(FeeConfigError::RecipientNotFound {
+        recipient: recipient.to_string(),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{Addr, Decimal};
+
+    use super::FeeConfigUpdate;
+    use crate::tests::test_fee_config;
+    use crate::{FeeConfig, FeeConfigError, Weight};
+
+    fn fee_config() -> FeeConfig<Addr> {
+        test_fee_config(Decimal::percent(1), &[("addr1", 50), ("addr2", 50)])
+    }
+
+    #[test]
+    fn apply_update() {
+        let deps = mock_dependencies();
+
+        let update = FeeConfigUpdate {
+            fee_rate: Some(Decimal::percent(2)),
+            remove_recipients: vec!["ADDR2".to_string()],
+            add_recipients: vec![("addr3".to_string().into(), Decimal::percent(25).into())],
+            set_recipient_weight: vec![("addr1".to_string(), Decimal::percent(75).into())],
+        };
+        let updated = fee_config().apply_update(&update, &deps.as_ref()).unwrap();
+        assert_eq!(
+            updated,
+            test_fee_config(Decimal::percent(2), &[("addr1", 75), ("addr3", 25)])
+        );
+
+        // An empty update leaves the config unchanged
+        let updated = fee_config()
+            .apply_update(&FeeConfigUpdate::default(), &deps.as_ref())
+            .unwrap();
+        assert_eq!(updated, fee_config());
+    }
+
+    #[test]
+    fn apply_update_validates_result() {
+        let deps = mock_dependencies();
+
+        let update = FeeConfigUpdate {
+            remove_recipients: vec!["addr3".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config()
+                .apply_update(&update, &deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::RecipientNotFound {
+                recipient: "addr3".to_string()
+            }
+        );
+
+        let update = FeeConfigUpdate {
+            set_recipient_weight: vec![(
+                "addr1".to_string(),
+                Weight::Percent(Decimal::percent(60)),
+            )],
+            ..Default::default()
+        };
+        assert_eq!(
+            fee_config()
+                .apply_update(&update, &deps.as_ref())
+                .unwrap_err(),
+            FeeConfigError::WeightsDoNotSumToOne {
+                sum: Decimal::percent(110)
+            }
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+
+use crate::FeeConfigError;
+
+#[cw_serde]
+#[derive(Copy)]
+#[serde(untagged)]
+/// The part of the fee that a fee recipient receives. All recipients of a fee
+/// config must use the same kind of weight.
+pub enum Weight {
+    /// A fraction of the fee. The fractions of all recipients must sum to 1.
+    /// Serialized as a decimal string, e.g. `"0.5"`.
+    Percent(Decimal),
+    /// A whole number of shares. Each recipient receives its shares divided by
+    /// the total number of shares of all recipients, which allows exact splits
+    /// like thirds. Serialized as a JSON number, e.g. `7`.
+    Shares(u32),
+}
+
+impl Weight {
+    /// Returns true if the weight is zero.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Weight::Shares(shares) => *shares == 0,
+            Weight::Percent(percent) => percent.is_zero(),
+        }
+    }
+
+    /// Adds two weights of the same kind. Returns an error if the kinds
+    /// differ or if the sum overflows.
+    pub fn checked_add(self, other: Weight) -> Result<Weight, FeeConfigError> {
+        match (self, other) {
+            (Weight::Shares(a), Weight::Shares(b)) => a
+                .checked_add(b)
+                .map(Weight::Shares)
+                .ok_or(FeeConfigError::SharesOverflow),
+            (Weight::Percent(a), Weight::Percent(b)) => Ok(Weight::Percent(a.checked_add(b)?)),
+            _ => Err(FeeConfigError::MixedWeights),
+        }
+    }
+}
+
+impl From<Decimal> for Weight {
+    fn from(percent: Decimal) -> Self {
+        Weight::Percent(percent)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use cosmwasm_std::{from_json, to_json_binary, Decimal};
+
+    use super::Weight;
+
+    #[test]
+    fn weight_serialization() {
+        assert_eq!(
+            to_json_binary(&Weight::Percent(Decimal::percent(50))).unwrap(),
+            to_json_binary(&"0.5").unwrap()
+        );
+        assert_eq!(
+            from_json::<Weight>(br#""0.5""#).unwrap(),
+            Weight::Percent(Decimal::percent(50))
+        );
+        assert_eq!(
+            to_json_binary(&Weight::Shares(7)).unwrap().to_vec(),
+            b"7".to_vec()
+        );
+        assert_eq!(from_json::<Weight>(b"7").unwrap(), Weight::Shares(7));
+    }
+}